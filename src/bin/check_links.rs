@@ -0,0 +1,94 @@
+use clap::Parser as ClapParser;
+use std::{fs, path::Path, process::ExitCode};
+
+use twir_deploy_notify::generator::{
+    GenerationOptions, Severity, check_links_online, extract_links, generate_posts_with_options,
+    lint_links, load_options, parse_report,
+};
+
+/// Check the links of the posts generated from a TWIR issue.
+#[derive(ClapParser)]
+struct Cli {
+    /// Input Markdown file
+    input: String,
+
+    /// TOML file with generation options for this delivery target
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Also send a HEAD request to every unique link
+    #[arg(long)]
+    online: bool,
+
+    /// Maximum number of requests in flight with --online
+    #[arg(long, default_value_t = 8)]
+    concurrency: usize,
+
+    /// Replace scheme and host of every link, e.g. http://127.0.0.1:8080
+    #[arg(long)]
+    base: Option<String>,
+
+    /// Treat warnings as errors
+    #[arg(long)]
+    strict: bool,
+}
+
+fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    env_logger::init();
+    let cli = Cli::parse();
+    let options = match cli.config.as_deref() {
+        Some(path) => load_options(Path::new(path))?,
+        None => GenerationOptions::default(),
+    };
+    let input = fs::read_to_string(&cli.input)?;
+    let report = parse_report(&input, &options);
+    let posts = generate_posts_with_options(input, &options).map_err(|e| format!("{e}"))?;
+    let links = extract_links(&posts);
+    println!("Found {} links in {} posts", links.len(), posts.len());
+
+    let mut failed = false;
+    for reference in &report.broken_references {
+        failed |= cli.strict;
+        println!("warning: broken link reference [{reference}]");
+    }
+    for html in &report.dropped_html {
+        failed |= cli.strict;
+        println!("warning: dropped raw HTML: {html}");
+    }
+    for report in lint_links(&links) {
+        let severity = report.problem.severity();
+        failed |= severity == Severity::Error || cli.strict;
+        let label = match severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        println!(
+            "{label}: post {}: {}: {}",
+            report.link.post + 1,
+            report.link.url,
+            report.problem
+        );
+    }
+
+    if cli.online {
+        let mut urls: Vec<String> = links.into_iter().map(|link| link.url).collect();
+        urls.sort();
+        urls.dedup();
+        for status in check_links_online(&urls, cli.concurrency, cli.base.as_deref()) {
+            if status.is_ok() {
+                continue;
+            }
+            failed = true;
+            match status.result {
+                Ok(code) => println!("error: {}: HTTP {code}", status.url),
+                Err(e) => println!("error: {}: {e}", status.url),
+            }
+        }
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
@@ -0,0 +1,61 @@
+use clap::{Parser, Subcommand};
+use std::{fs, path::Path};
+
+use twir_deploy_notify::generator::{GenerationOptions, load_options, load_rules, preview_rules};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show the sections of an issue before and after the rules
+    Test {
+        /// Input Markdown file
+        input: String,
+
+        /// TOML file with generation options, including any `[[rules]]`
+        #[arg(long)]
+        config: Option<String>,
+
+        /// TOML file with `[[rules]]` added after those of the config file
+        #[arg(long)]
+        rules: Option<String>,
+    },
+}
+
+fn main() -> std::io::Result<()> {
+    env_logger::init();
+    let Command::Test {
+        input,
+        config,
+        rules,
+    } = Cli::parse().command;
+    let mut options = match config.as_deref() {
+        Some(path) => load_options(Path::new(path))?,
+        None => GenerationOptions::default(),
+    };
+    if let Some(path) = rules.as_deref() {
+        options.rules.extend(load_rules(Path::new(path))?);
+    }
+    let input = fs::read_to_string(input)?;
+    let changes = preview_rules(&input, &options);
+    if changes.is_empty() {
+        println!("The rules change no section");
+    }
+    for change in changes {
+        println!("=== {} ===", change.title);
+        println!("--- before");
+        for line in &change.before {
+            println!("{line}");
+        }
+        println!("+++ after");
+        for line in &change.after {
+            println!("{line}");
+        }
+        println!();
+    }
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use reqwest::blocking::Client;
 use serde_json::Value;
-use std::{collections::VecDeque, env, fs, thread, time::Duration};
+use std::{collections::VecDeque, env, fs, path::Path, thread, time::Duration};
 
-use twir_deploy_notify::generator::{generate_posts, normalize_chat_id};
+use twir_deploy_notify::generator::{
+    GenerationOptions, generate_posts_with_options, load_options, normalize_chat_id,
+};
 
 const MAX_ATTEMPTS: usize = 10;
 const POLL_DELAY: Duration = Duration::from_secs(2);
@@ -10,8 +12,12 @@ const ALLOWED_UPDATES: &str = r#"["message","channel_post"]"#;
 
 fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let path = std::env::args().nth(1).expect("missing input file");
+    let options = match std::env::args().nth(2) {
+        Some(config) => load_options(Path::new(&config))?,
+        None => GenerationOptions::default(),
+    };
     let input = fs::read_to_string(path)?;
-    let posts = generate_posts(input).map_err(|e| format!("{e}"))?;
+    let posts = generate_posts_with_options(input, &options).map_err(|e| format!("{e}"))?;
     if posts.is_empty() {
         println!("No posts generated; skipping Telegram verification");
         return Ok(());
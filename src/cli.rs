@@ -8,9 +8,37 @@ use std::{
 struct Credentials {
     token: String,
     chat_id: String,
+    /// Forum topic of this chat that receives the messages.
+    thread_id: Option<i64>,
 }
 
-use crate::generator::{generate_posts, markdown_to_plain, send_to_telegram, write_posts};
+impl Credentials {
+    /// Threading settings for this chat, with its own forum topic.
+    fn threading(&self, options: &ThreadingOptions) -> ThreadingOptions {
+        ThreadingOptions {
+            message_thread_id: self.thread_id,
+            ..options.clone()
+        }
+    }
+}
+
+use crate::generator::{
+    DeliveryReport, GenerationOptions, Locale, PackingStrategy, TELEGRAM_LIMIT, ThreadingOptions,
+    TocEntry, edit_message_text, generate_calendar, generate_cover, generate_document,
+    generate_issue, generate_quote_card, generate_toc, load_options, load_rules, load_theme,
+    markdown_to_plain, render_toc, send_photo_to_telegram, send_to_telegram_threaded,
+    write_calendar, write_document, write_photo, write_posts,
+};
+
+/// Kind of output produced from the issue.
+#[derive(Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+enum OutputFormat {
+    /// Telegram posts written to `output_N.md` and delivered to the chats
+    #[default]
+    Telegram,
+    /// Structured issue document written to `issue.json`; nothing is sent
+    Json,
+}
 
 #[derive(ClapParser)]
 struct Cli {
@@ -20,6 +48,34 @@ struct Cli {
     /// Generate plain text output
     #[arg(long)]
     plain: bool,
+
+    /// How sections are distributed across posts (overrides the config file)
+    #[arg(long, value_enum)]
+    packing: Option<PackingStrategy>,
+
+    /// TOML file with generation options for this delivery target
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Language of generated labels and section titles (overrides the config file)
+    #[arg(long, value_enum)]
+    locale: Option<Locale>,
+
+    /// TOML file with a heading theme (overrides the config file)
+    #[arg(long)]
+    theme: Option<String>,
+
+    /// TOML file with `[[rules]]` applied after those of the config file
+    #[arg(long)]
+    rules: Option<String>,
+
+    /// Also export Upcoming Events to events.ics
+    #[arg(long)]
+    ics: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Telegram)]
+    format: OutputFormat,
 }
 
 /// Entry point for the command-line interface.
@@ -36,9 +92,49 @@ pub fn main() -> std::io::Result<()> {
 
     log::info!("Reading input file {}", cli.input);
     let input = fs::read_to_string(&cli.input)?;
+    let mut options = match cli.config.as_deref() {
+        Some(path) => {
+            log::info!("Loading generation options from {path}");
+            load_options(Path::new(path))?
+        }
+        None => GenerationOptions::default(),
+    };
+    if let Some(packing) = cli.packing {
+        options.packing = packing;
+    }
+    if let Some(locale) = cli.locale {
+        options.locale = locale;
+    }
+    if let Some(path) = cli.theme.as_deref() {
+        log::info!("Loading theme from {path}");
+        options.theme = load_theme(Path::new(path))?;
+    }
+    if let Some(path) = cli.rules.as_deref() {
+        log::info!("Loading rules from {path}");
+        options.rules.extend(load_rules(Path::new(path))?);
+    }
+    if cli.format == OutputFormat::Json {
+        log::info!("Generating issue document");
+        return write_document(&generate_document(&input, &options), Path::new("."));
+    }
+
     log::info!("Generating posts");
-    let mut posts = generate_posts(input).map_err(|e| std::io::Error::other(e.to_string()))?;
-    log::info!("Generated {} posts", posts.len());
+    let calendar = if cli.ics {
+        generate_calendar(&input, &options)
+    } else {
+        None
+    };
+    let issue = generate_issue(input.clone(), &options)
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    log::info!("Generated {} posts", issue.posts.len());
+    let cover = generate_cover(&input, &issue, &options)?;
+    let card = generate_quote_card(&input, &options)?;
+    let toc = options
+        .toc
+        .as_ref()
+        .map(|_| generate_toc(&issue, &options))
+        .unwrap_or_default();
+    let mut posts = issue.posts;
 
     if cli.plain {
         log::info!("Converting posts to plain text");
@@ -47,6 +143,17 @@ pub fn main() -> std::io::Result<()> {
 
     log::info!("Writing posts to disk");
     write_posts(&posts, Path::new("."))?;
+    if let Some(calendar) = calendar {
+        write_calendar(&calendar, Path::new("."))?;
+    } else if cli.ics {
+        log::info!("No upcoming events found; skipping calendar export");
+    }
+    if let Some(cover) = cover.as_ref().filter(|_| options.cover.image.is_none()) {
+        write_photo(cover, Path::new("."))?;
+    }
+    if let Some(card) = &card {
+        write_photo(card, Path::new("."))?;
+    }
 
     if posts.is_empty() {
         log::info!("No posts generated; skipping Telegram delivery");
@@ -61,17 +168,33 @@ pub fn main() -> std::io::Result<()> {
             "Developer Telegram send skipped via TWIR_SKIP_DEVELOPER_SEND environment variable",
         );
     } else {
-        let creds = read_credentials_pair(("DEV_BOT_TOKEN", "DEV_CHAT_ID"), "developer Telegram")?;
+        let mut creds =
+            read_credentials_pair(("DEV_BOT_TOKEN", "DEV_CHAT_ID"), "developer Telegram")?;
+        creds.thread_id = read_thread_id("DEV_THREAD_ID")?;
 
         log::debug!("developer chat id: {}", creds.chat_id);
+        if let Some(cover) = &cover {
+            log::info!("Sending cover to developer Telegram chat");
+            send_photo_to_telegram(
+                cover,
+                &base,
+                &creds.token,
+                &creds.chat_id,
+                !cli.plain,
+                false,
+                creds.thread_id,
+            )
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        }
         log::info!("Sending posts to developer Telegram chat");
-        let report = send_to_telegram(
+        let report = send_to_telegram_threaded(
             &posts,
             &base,
             &creds.token,
             &creds.chat_id,
             !cli.plain,
             false,
+            &creds.threading(&options.threading),
         )
         .map_err(|e| io::Error::other(e.to_string()))?;
         if !report.all_confirmed(posts.len()) {
@@ -84,6 +207,20 @@ pub fn main() -> std::io::Result<()> {
                 "Developer Telegram failed to confirm every post; aborting production delivery",
             ));
         }
+        deliver_toc(&toc, &posts, &report, &options, &base, &creds, !cli.plain)?;
+        if let Some(card) = &card {
+            log::info!("Sending quote card to developer Telegram chat");
+            send_photo_to_telegram(
+                card,
+                &base,
+                &creds.token,
+                &creds.chat_id,
+                !cli.plain,
+                false,
+                creds.thread_id,
+            )
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        }
         log::info!(
             "Developer delivery confirmed for {} posts; preparing production stage",
             report.confirmed
@@ -97,18 +234,34 @@ pub fn main() -> std::io::Result<()> {
         return Ok(());
     }
 
-    let production_credentials =
+    let mut production_credentials =
         read_credentials_pair(("PROD_BOT_TOKEN", "PROD_CHAT_ID"), "production Telegram")?;
+    production_credentials.thread_id =
+        read_thread_id("PROD_THREAD_ID")?.or(options.threading.message_thread_id);
 
     log::debug!("production chat id: {}", production_credentials.chat_id);
+    if let Some(cover) = &cover {
+        log::info!("Sending cover to production Telegram chat");
+        send_photo_to_telegram(
+            cover,
+            &base,
+            &production_credentials.token,
+            &production_credentials.chat_id,
+            !cli.plain,
+            true,
+            production_credentials.thread_id,
+        )
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    }
     log::info!("Sending posts to production Telegram chat");
-    let production_report = send_to_telegram(
+    let production_report = send_to_telegram_threaded(
         &posts,
         &base,
         &production_credentials.token,
         &production_credentials.chat_id,
         !cli.plain,
-        true,
+        cover.is_none(),
+        &production_credentials.threading(&options.threading),
     )
     .map_err(|e| io::Error::other(e.to_string()))?;
     if !production_report.all_confirmed(posts.len()) {
@@ -121,10 +274,86 @@ pub fn main() -> std::io::Result<()> {
             "Production Telegram failed to confirm every post",
         ));
     }
+    deliver_toc(
+        &toc,
+        &posts,
+        &production_report,
+        &options,
+        &base,
+        &production_credentials,
+        !cli.plain,
+    )?;
+    if let Some(card) = &card {
+        log::info!("Sending quote card to production Telegram chat");
+        send_photo_to_telegram(
+            card,
+            &base,
+            &production_credentials.token,
+            &production_credentials.chat_id,
+            !cli.plain,
+            false,
+            production_credentials.thread_id,
+        )
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    }
 
     Ok(())
 }
 
+/// Send the table of contents of delivered `posts`, or append it to the
+/// first post with `[toc] edit_first` when it still fits.
+fn deliver_toc(
+    toc: &[TocEntry],
+    posts: &[String],
+    report: &DeliveryReport,
+    options: &GenerationOptions,
+    base: &str,
+    creds: &Credentials,
+    use_markdown: bool,
+) -> io::Result<()> {
+    let Some(settings) = &options.toc else {
+        return Ok(());
+    };
+    let messages = options.locale.messages();
+    let Some(mut text) = render_toc(toc, &report.message_ids, &creds.chat_id, messages) else {
+        log::info!("No linkable sections; skipping table of contents");
+        return Ok(());
+    };
+    if !use_markdown {
+        text = markdown_to_plain(&text);
+    }
+    if settings.edit_first
+        && let (Some(first), Some(&id)) = (posts.first(), report.message_ids.first())
+    {
+        let edited = format!("{}\n\n{text}", first.trim_end());
+        if edited.len() <= TELEGRAM_LIMIT {
+            log::info!("Adding table of contents to message {id}");
+            return edit_message_text(
+                &edited,
+                base,
+                &creds.token,
+                &creds.chat_id,
+                id,
+                use_markdown,
+            )
+            .map_err(|e| io::Error::other(e.to_string()));
+        }
+        log::warn!("Table of contents does not fit the first post; sending it separately");
+    }
+    log::info!("Sending table of contents");
+    send_to_telegram_threaded(
+        &[text],
+        base,
+        &creds.token,
+        &creds.chat_id,
+        use_markdown,
+        false,
+        &creds.threading(&options.threading),
+    )
+    .map(|_| ())
+    .map_err(|e| io::Error::other(e.to_string()))
+}
+
 fn read_optional_env(name: &str) -> io::Result<Option<String>> {
     match env::var(name) {
         Ok(value) if !value.trim().is_empty() => Ok(Some(value)),
@@ -140,6 +369,19 @@ fn read_optional_env(name: &str) -> io::Result<Option<String>> {
     }
 }
 
+fn read_thread_id(name: &str) -> io::Result<Option<i64>> {
+    let Some(value) = read_optional_env(name)? else {
+        return Ok(None);
+    };
+    match value.trim().parse() {
+        Ok(id) => Ok(Some(id)),
+        Err(_) => {
+            log::error!("{name} must be a forum topic identifier; got {value}");
+            Err(io::Error::other(format!("{name} must be an integer")))
+        }
+    }
+}
+
 fn read_bool_flag(name: &str) -> io::Result<bool> {
     match env::var(name) {
         Ok(value) => {
@@ -168,7 +410,11 @@ fn read_bool_flag(name: &str) -> io::Result<bool> {
 
 fn read_credentials_pair(names: (&str, &str), label: &str) -> io::Result<Credentials> {
     match read_pair(names)? {
-        PairState::Complete(token, chat_id) => Ok(Credentials { token, chat_id }),
+        PairState::Complete(token, chat_id) => Ok(Credentials {
+            token,
+            chat_id,
+            thread_id: None,
+        }),
         PairState::Missing => {
             log::error!("{label} credentials not provided");
             Err(io::Error::other(format!(
@@ -1,9 +1,3 @@
-mod cli;
-mod generator;
-mod parser;
-mod shared;
-mod validator;
-
 fn main() -> std::io::Result<()> {
-    cli::main()
+    twir_deploy_notify::cli::main()
 }
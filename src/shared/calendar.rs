@@ -0,0 +1,254 @@
+use std::{fs, io, path::Path};
+
+use crate::shared::events::{Attendance, Event};
+
+/// File name of the calendar written next to the generated posts.
+pub const CALENDAR_FILE: &str = "events.ics";
+
+/// Maximum length of a content line in octets before it must be folded.
+const LINE_LIMIT: usize = 75;
+
+/// Parse a `YYYY-MM-DD` date.
+fn parse_date(date: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = date.trim().splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    ((1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month))
+        .then_some((year, month, day))
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn next_day((year, month, day): (u32, u32, u32)) -> (u32, u32, u32) {
+    if day < days_in_month(year, month) {
+        (year, month, day + 1)
+    } else if month < 12 {
+        (year, month + 1, 1)
+    } else {
+        (year + 1, 1, 1)
+    }
+}
+
+fn compact((year, month, day): (u32, u32, u32)) -> String {
+    format!("{year:04}{month:02}{day:02}")
+}
+
+/// Escape a TEXT property value.
+fn escape_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' | ';' | ',' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Append `line` folded at 75 octets and terminated with CRLF.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > LINE_LIMIT {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(ch);
+        width += ch.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// FNV-1a hash used to derive stable event identifiers.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn attendance_label(attendance: Attendance) -> &'static str {
+    match attendance {
+        Attendance::InPerson => "In person",
+        Attendance::Virtual => "Virtual",
+        Attendance::Hybrid => "Hybrid",
+    }
+}
+
+struct CalendarEntry<'a> {
+    event: &'a Event,
+    start: (u32, u32, u32),
+    end: (u32, u32, u32),
+    categories: Vec<&'a str>,
+}
+
+/// Convert `events` into an iCalendar document with one all-day `VEVENT`
+/// per event.
+///
+/// Events listed under several regions are emitted once with every region
+/// as a category. Events with unparsable dates are skipped.
+///
+/// # Parameters
+/// - `events`: Events from [`parse_events`](crate::shared::events::parse_events).
+/// - `name`: Calendar display name.
+/// - `stamp`: Issue date in `YYYY-MM-DD` form used as `DTSTAMP`, which keeps
+///   the output reproducible.
+///
+/// # Returns
+/// The calendar text with CRLF line endings.
+pub fn events_to_ical(events: &[Event], name: &str, stamp: Option<&str>) -> String {
+    let mut entries: Vec<CalendarEntry<'_>> = Vec::new();
+    for event in events {
+        let Some(start) = parse_date(&event.start) else {
+            log::warn!("Skipping event with invalid date {:?}", event.start);
+            continue;
+        };
+        let last = event.end.as_deref().and_then(parse_date).unwrap_or(start);
+        if let Some(existing) = entries
+            .iter_mut()
+            .find(|entry| entry.event.url == event.url && entry.start == start)
+        {
+            if !existing.categories.contains(&event.region.as_str()) {
+                existing.categories.push(&event.region);
+            }
+            continue;
+        }
+        entries.push(CalendarEntry {
+            event,
+            start,
+            end: next_day(last.max(start)),
+            categories: vec![&event.region],
+        });
+    }
+
+    let stamp = stamp
+        .and_then(parse_date)
+        .or_else(|| entries.first().map(|entry| entry.start))
+        .map_or_else(|| "19700101".to_string(), compact);
+
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(
+        &mut out,
+        "PRODID:-//twir-deploy-notify//Upcoming Events//EN",
+    );
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, &format!("X-WR-CALNAME:{}", escape_text(name)));
+    for entry in &entries {
+        let event = entry.event;
+        let mut categories = entry.categories.clone();
+        let label = attendance_label(event.attendance);
+        if !categories.contains(&label) {
+            categories.push(label);
+        }
+        let categories: Vec<String> = categories.iter().map(|c| escape_text(c)).collect();
+        let groups: Vec<&str> = event.groups.iter().map(|g| g.text.as_str()).collect();
+
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(
+            &mut out,
+            &format!(
+                "UID:{:016x}@this-week-in-rust.org",
+                fnv1a(&format!("{}|{}", event.start, event.url))
+            ),
+        );
+        push_line(&mut out, &format!("DTSTAMP:{stamp}T000000Z"));
+        push_line(
+            &mut out,
+            &format!("DTSTART;VALUE=DATE:{}", compact(entry.start)),
+        );
+        push_line(
+            &mut out,
+            &format!("DTEND;VALUE=DATE:{}", compact(entry.end)),
+        );
+        push_line(&mut out, &format!("SUMMARY:{}", escape_text(&event.title)));
+        if !event.location.is_empty() {
+            push_line(
+                &mut out,
+                &format!("LOCATION:{}", escape_text(&event.location)),
+            );
+        }
+        if !groups.is_empty() {
+            push_line(
+                &mut out,
+                &format!(
+                    "DESCRIPTION:{}",
+                    escape_text(&format!("Hosted by {}", groups.join(", ")))
+                ),
+            );
+        }
+        push_line(&mut out, &format!("URL:{}", event.url));
+        push_line(&mut out, &format!("CATEGORIES:{}", categories.join(",")));
+        push_line(&mut out, "END:VEVENT");
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// Write `calendar` to [`CALENDAR_FILE`] in `dir`.
+///
+/// # Returns
+/// `Ok(())` on success or any file I/O error encountered.
+pub fn write_calendar(calendar: &str, dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(CALENDAR_FILE);
+    fs::write(&path, calendar)?;
+    println!("Generated {CALENDAR_FILE}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::events::parse_events;
+
+    const EVENTS: &str = "## Upcoming Events\n\n### Virtual\n* 2025-10-23 | Hybrid (Seattle/Bellevue, WA, US) | [Seattle Rust User Group](https://www.meetup.com/join-srug)\n    * [**SRUG Meetup**](https://www.meetup.com/seattle-rust-user-group/events/3/)\n\n### Europe\n* 2025-12-30 - 2026-01-01 | Florence, IT | [Rustlab 2025](https://rustlab.it/)\n    * $[**Rustlab; 2025**](https://rustlab.it/)\n* 2025-13-01 | Nowhere | [Broken](https://example.com/)\n\n### North America\n* 2025-10-23 | Hybrid (Seattle/Bellevue, WA, US) | [Seattle Rust User Group](https://www.meetup.com/join-srug)\n    * [**SRUG Meetup**](https://www.meetup.com/seattle-rust-user-group/events/3/)\n";
+
+    #[test]
+    fn date_ranges_end_the_day_after() {
+        assert_eq!(next_day((2024, 2, 28)), (2024, 2, 29));
+        assert_eq!(next_day((2025, 2, 28)), (2025, 3, 1));
+        assert_eq!(next_day((2025, 12, 31)), (2026, 1, 1));
+        assert_eq!(parse_date("2025-02-29"), None);
+    }
+
+    #[test]
+    fn builds_one_vevent_per_event() {
+        let calendar = events_to_ical(&parse_events(EVENTS), "TWIR", Some("2025-10-22"));
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 2);
+        assert!(calendar.contains("CATEGORIES:Virtual,North America,Hybrid\r\n"));
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20251230\r\n"));
+        assert!(calendar.contains("DTEND;VALUE=DATE:20260102\r\n"));
+        assert!(calendar.contains("SUMMARY:Rustlab\\; 2025\r\n"));
+        assert!(calendar.contains("LOCATION:Florence\\, IT\r\n"));
+        assert!(calendar.contains("CATEGORIES:Europe,In person\r\n"));
+        assert!(calendar.contains("DTSTAMP:20251022T000000Z\r\n"));
+        assert!(!calendar.contains("Broken"));
+    }
+
+    #[test]
+    fn long_lines_are_folded() {
+        let mut out = String::new();
+        push_line(&mut out, &"é".repeat(60));
+        let lines: Vec<&str> = out.split("\r\n").collect();
+        assert!(lines.iter().all(|line| line.len() <= LINE_LIMIT));
+        assert!(lines[1].starts_with(' '));
+    }
+}
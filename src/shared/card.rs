@@ -0,0 +1,243 @@
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont, point};
+use serde::Deserialize;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// An image sent with `sendPhoto` and its Telegram Markdown caption.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhotoPost {
+    pub image: Vec<u8>,
+    /// Name of the uploaded file, e.g. `cover.png`.
+    pub file_name: String,
+    pub caption: String,
+}
+
+/// Write the image of `photo` to its file name in `dir`.
+///
+/// # Errors
+/// Returns an error if the file cannot be written.
+pub fn write_photo(photo: &PhotoPost, dir: &Path) -> io::Result<()> {
+    fs::write(dir.join(&photo.file_name), &photo.image)
+}
+
+/// Look of a generated image card.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CardOptions {
+    /// TrueType or OpenType font used for all text.
+    pub font: PathBuf,
+    /// Image width in pixels; the height follows the text.
+    #[serde(default = "default_card_width")]
+    pub width: u32,
+    /// Colors as `#rrggbb`.
+    #[serde(default = "default_background")]
+    pub background: String,
+    #[serde(default = "default_foreground")]
+    pub foreground: String,
+    /// Color of the title and the bar beside the quote.
+    #[serde(default = "default_accent")]
+    pub accent: String,
+}
+
+fn default_card_width() -> u32 {
+    1080
+}
+
+fn default_background() -> String {
+    "#fffaf0".to_string()
+}
+
+fn default_foreground() -> String {
+    "#222222".to_string()
+}
+
+fn default_accent() -> String {
+    "#ce422b".to_string()
+}
+
+/// Font and colors of a card, loaded from [`CardOptions`].
+pub(crate) struct CardStyle {
+    pub(crate) font: FontVec,
+    pub(crate) background: [u8; 3],
+    pub(crate) foreground: [u8; 3],
+    pub(crate) accent: [u8; 3],
+}
+
+impl CardOptions {
+    /// Load the font and parse the colors.
+    ///
+    /// # Errors
+    /// Returns an error if the font cannot be read or parsed, a color is not
+    /// `#rrggbb` or the width is below 320 pixels.
+    pub(crate) fn style(&self) -> io::Result<CardStyle> {
+        if self.width < 320 {
+            return Err(io::Error::other("card width must be at least 320"));
+        }
+        let font = FontVec::try_from_vec(fs::read(&self.font)?)
+            .map_err(|e| io::Error::other(format!("invalid font {}: {e}", self.font.display())))?;
+        Ok(CardStyle {
+            font,
+            background: parse_color(&self.background)?,
+            foreground: parse_color(&self.foreground)?,
+            accent: parse_color(&self.accent)?,
+        })
+    }
+}
+
+/// Break `text` into lines no wider than `max_width` as reported by `width`.
+///
+/// A word wider than `max_width` is kept on a line of its own.
+pub(crate) fn wrap_text(text: &str, max_width: f32, width: impl Fn(&str) -> f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current} {word}")
+        };
+        if width(&candidate) > max_width && !current.is_empty() {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        } else {
+            current = candidate;
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn parse_color(color: &str) -> io::Result<[u8; 3]> {
+    let hex = color.trim().trim_start_matches('#');
+    let channel = |at: usize| {
+        hex.get(at..at + 2)
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+    };
+    match (hex.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Ok([r, g, b]),
+        _ => Err(io::Error::other(format!(
+            "invalid color {color:?}, expected #rrggbb"
+        ))),
+    }
+}
+
+/// RGB canvas a card is drawn on.
+pub(crate) struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    pub(crate) fn new(width: u32, height: u32, background: [u8; 3]) -> Self {
+        let pixels = background.repeat((width * height) as usize);
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Mix `color` into the pixel at `(x, y)` with `coverage` from 0 to 1.
+    pub(crate) fn blend(&mut self, x: i32, y: i32, color: [u8; 3], coverage: f32) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return;
+        }
+        let at = (y as usize * self.width as usize + x as usize) * 3;
+        let coverage = coverage.clamp(0.0, 1.0);
+        for (pixel, channel) in self.pixels[at..at + 3].iter_mut().zip(color) {
+            *pixel = (*pixel as f32 * (1.0 - coverage) + channel as f32 * coverage).round() as u8;
+        }
+    }
+
+    pub(crate) fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+        for py in y..(y + height).min(self.height) {
+            for px in x..(x + width).min(self.width) {
+                self.blend(px as i32, py as i32, color, 1.0);
+            }
+        }
+    }
+
+    /// Draw `text` with its baseline at `y`.
+    pub(crate) fn text(
+        &mut self,
+        font: &FontVec,
+        size: f32,
+        x: f32,
+        y: f32,
+        text: &str,
+        color: [u8; 3],
+    ) {
+        let scaled = font.as_scaled(PxScale::from(size));
+        let mut caret = x;
+        let mut previous = None;
+        for c in text.chars() {
+            let id = scaled.glyph_id(c);
+            if let Some(previous) = previous {
+                caret += scaled.kern(previous, id);
+            }
+            let glyph = id.with_scale_and_position(size, point(caret, y));
+            caret += scaled.h_advance(id);
+            previous = Some(id);
+            if let Some(outline) = font.outline_glyph(glyph) {
+                let bounds = outline.px_bounds();
+                outline.draw(|gx, gy, coverage| {
+                    self.blend(
+                        bounds.min.x as i32 + gx as i32,
+                        bounds.min.y as i32 + gy as i32,
+                        color,
+                        coverage,
+                    );
+                });
+            }
+        }
+    }
+
+    pub(crate) fn encode(&self) -> io::Result<Vec<u8>> {
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)?;
+        Ok(png)
+    }
+}
+
+pub(crate) fn text_width(font: &FontVec, size: f32, text: &str) -> f32 {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let mut width = 0.0;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            width += scaled.kern(previous, id);
+        }
+        width += scaled.h_advance(id);
+        previous = Some(id);
+    }
+    width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_words_to_width() {
+        let lines = wrap_text("aa bb cc dddddd e", 5.0, |text| text.len() as f32);
+        assert_eq!(lines, vec!["aa bb", "cc", "dddddd", "e"]);
+    }
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_color("#ce422b").unwrap(), [0xce, 0x42, 0x2b]);
+        assert!(parse_color("red").is_err());
+    }
+}
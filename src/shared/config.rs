@@ -0,0 +1,160 @@
+use serde::Deserialize;
+use std::{fs, io, path::Path};
+
+use crate::shared::cover::CoverOptions;
+use crate::shared::crates::CrateIndexOptions;
+use crate::shared::events::EventDigest;
+use crate::shared::fcp::FcpOptions;
+use crate::shared::glossary::Glossary;
+use crate::shared::jobs::JobsOptions;
+use crate::shared::locale::Locale;
+use crate::shared::packing::PackingStrategy;
+use crate::shared::pulls::PullOptions;
+use crate::shared::quote::QuoteOptions;
+use crate::shared::rules::Rule;
+use crate::shared::sections::SectionPolicy;
+use crate::shared::tags::TagOptions;
+use crate::shared::theme::Theme;
+use crate::shared::threading::ThreadingOptions;
+use crate::shared::toc::TocOptions;
+use crate::shared::transforms::TransformOptions;
+use crate::shared::translate::TranslateOptions;
+use crate::shared::urls::UrlOptions;
+
+/// Options controlling how an issue is turned into posts.
+///
+/// Each delivery target can use its own options, typically loaded from a
+/// TOML file with [`load_options`].
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GenerationOptions {
+    /// How rendered sections are distributed across posts.
+    pub packing: PackingStrategy,
+    /// Which sections are published and in what order.
+    pub sections: SectionPolicy,
+    /// Regional digest of the "Upcoming Events" section.
+    pub events: Option<EventDigest>,
+    /// Final Comment Period rendering.
+    pub fcp: FcpOptions,
+    /// Merged pull request digest.
+    pub pulls: PullOptions,
+    /// Local crates index used to enrich the Crate of the Week.
+    pub crates: CrateIndexOptions,
+    /// Link rewriting applied while parsing.
+    pub urls: UrlOptions,
+    /// Icons for media tags and tag-based item filtering.
+    pub tags: TagOptions,
+    /// Heading emojis, case, style and separators.
+    pub theme: Theme,
+    /// Language of generated labels and section titles.
+    pub locale: Locale,
+    /// Machine translation of section content.
+    pub translate: Option<TranslateOptions>,
+    /// Terms kept verbatim by translation, heading case and splitting.
+    pub glossary: Glossary,
+    /// Photo sent before the issue posts.
+    pub cover: CoverOptions,
+    /// Replies and forum topic grouping the posts of an issue.
+    pub threading: ThreadingOptions,
+    /// Table of contents linking the section headings after delivery.
+    pub toc: Option<TocOptions>,
+    /// Standalone post or image card of the Quote of the Week.
+    pub quote: QuoteOptions,
+    /// Links and postings of the Jobs section.
+    pub jobs: JobsOptions,
+    /// Built-in section transforms to skip.
+    pub transforms: TransformOptions,
+    /// Declarative line rewrites applied after the section transforms.
+    pub rules: Vec<Rule>,
+}
+
+/// Read [`GenerationOptions`] from the TOML file at `path`.
+///
+/// Relative file paths, such as `[crates] index`, `[jobs] thread` or
+/// `[quote.card] font`, are resolved against the directory of `path`.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or does not match the
+/// expected schema.
+pub fn load_options(path: &Path) -> io::Result<GenerationOptions> {
+    let text = fs::read_to_string(path)?;
+    let mut options = parse_options(&text)
+        .map_err(|e| io::Error::other(format!("invalid config {}: {e}", path.display())))?;
+    let relative = [
+        options.crates.index.as_mut(),
+        options
+            .translate
+            .as_mut()
+            .and_then(|translate| translate.memory.as_mut()),
+        options.jobs.thread.as_mut(),
+        options.quote.card.as_mut().map(|card| &mut card.font),
+        options.cover.image.as_mut(),
+        options.cover.card.as_mut().map(|card| &mut card.font),
+    ];
+    for file in relative.into_iter().flatten() {
+        if file.is_relative()
+            && let Some(dir) = path.parent()
+        {
+            *file = dir.join(&*file);
+        }
+    }
+    Ok(options)
+}
+
+/// Parse [`GenerationOptions`] from TOML text.
+pub fn parse_options(text: &str) -> Result<GenerationOptions, toml::de::Error> {
+    toml::from_str(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let options = parse_options("").unwrap();
+        assert_eq!(options.packing, PackingStrategy::Greedy);
+        assert!(options.sections.exclude.is_empty());
+    }
+
+    #[test]
+    fn parses_section_policy() {
+        let options = parse_options(
+            r#"
+packing = "balanced"
+
+[sections]
+include = ["Crate of the Week", "Official updates", "Quote of the Week"]
+order = ["Quote of the Week"]
+aliases = { "official updates" = "Official" }
+truncate = [{ title = "Project/Tooling Updates", max_items = 10 }]
+"#,
+        )
+        .unwrap();
+        assert_eq!(options.packing, PackingStrategy::Balanced);
+        assert_eq!(options.sections.include.len(), 3);
+        assert_eq!(options.sections.truncate[0].max_items, 10);
+    }
+
+    #[test]
+    fn parses_event_digest() {
+        let options = parse_options(
+            r#"
+[events]
+regions = ["Europe"]
+cities = ["Berlin"]
+standalone = true
+"#,
+        )
+        .unwrap();
+        let events = options.events.unwrap();
+        assert_eq!(events.regions, vec!["Europe"]);
+        assert!(events.standalone);
+        assert!(parse_options("").unwrap().events.is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(parse_options("colour = \"red\"").is_err());
+    }
+}
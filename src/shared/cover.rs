@@ -0,0 +1,135 @@
+use serde::Deserialize;
+use std::{io, path::PathBuf};
+use teloxide::utils::markdown::escape;
+
+use crate::shared::card::{Canvas, CardOptions, CardStyle, text_width};
+
+/// File name of the generated cover.
+pub const COVER_FILE: &str = "cover.png";
+
+/// Photo sent before the issue posts.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CoverOptions {
+    /// Image file sent as the cover, e.g. the channel artwork.
+    pub image: Option<PathBuf>,
+    /// Draw the cover from the issue metadata when no `image` is set.
+    pub card: Option<CardOptions>,
+}
+
+/// What the cover shows, as plain text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cover {
+    /// Issue title, e.g. `This Week in Rust 606`.
+    pub title: String,
+    /// Issue number and date, e.g. `#606 — 2025-07-02`.
+    pub subtitle: String,
+    /// Titles of the published sections.
+    pub sections: Vec<String>,
+}
+
+impl Cover {
+    /// Render the Telegram Markdown caption with at most `limit` characters.
+    ///
+    /// Sections that do not fit are replaced by an ellipsis.
+    pub(crate) fn caption(&self, limit: usize) -> String {
+        let mut caption = format!("**{}**", escape(&self.title));
+        if !self.subtitle.is_empty() {
+            caption.push_str(&format!("\n{}", escape(&self.subtitle)));
+        }
+        if !self.sections.is_empty() {
+            caption.push('\n');
+        }
+        let mut length = caption.chars().count();
+        for title in &self.sections {
+            let line = format!("\n• {}", escape(title));
+            let line_length = line.chars().count();
+            if length + line_length + "\n…".chars().count() > limit {
+                caption.push_str("\n…");
+                break;
+            }
+            caption.push_str(&line);
+            length += line_length;
+        }
+        caption
+    }
+}
+
+/// Render `cover` as a PNG image.
+///
+/// The image is at least 16:9; a long section list makes it taller.
+///
+/// # Errors
+/// Returns an error if the font cannot be read or parsed, a color is not
+/// `#rrggbb` or the width is below 320 pixels.
+pub fn render_cover(cover: &Cover, options: &CardOptions) -> io::Result<Vec<u8>> {
+    let CardStyle {
+        font,
+        background,
+        foreground,
+        accent,
+    } = options.style()?;
+    let width = options.width as f32;
+    let padding = width / 14.0;
+    let title_size = width / 14.0;
+    let subtitle_size = width / 28.0;
+    let item_size = width / 36.0;
+    let item_height = item_size * 1.6;
+
+    let title_size = title_size.min((width - padding * 2.0) / text_width(&font, 1.0, &cover.title));
+    let header_height = title_size * 1.3 + subtitle_size * 2.2;
+    let list_height = cover.sections.len() as f32 * item_height;
+    let height = (padding * 2.0 + header_height + item_size + list_height)
+        .max(width * 9.0 / 16.0)
+        .ceil() as u32;
+
+    let mut canvas = Canvas::new(options.width, height, background);
+    let mut y = padding + title_size;
+    canvas.text(&font, title_size, padding, y, &cover.title, foreground);
+    y += subtitle_size * 1.6;
+    canvas.text(&font, subtitle_size, padding, y, &cover.subtitle, accent);
+    y += subtitle_size * 0.8;
+    let bar = (width / 200.0).max(4.0);
+    canvas.fill(
+        padding as u32,
+        y as u32,
+        (width / 6.0) as u32,
+        bar as u32,
+        accent,
+    );
+    y += item_size + item_height;
+    for title in &cover.sections {
+        canvas.text(&font, item_size, padding, y, title, foreground);
+        y += item_height;
+    }
+    canvas.encode()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cover(sections: usize) -> Cover {
+        Cover {
+            title: "This Week in Rust 606".into(),
+            subtitle: "#606 — 2025-07-02".into(),
+            sections: (1..=sections).map(|n| format!("Section {n}")).collect(),
+        }
+    }
+
+    #[test]
+    fn caption_lists_sections() {
+        assert_eq!(
+            cover(2).caption(1024),
+            "**This Week in Rust 606**\n\\#606 — 2025\\-07\\-02\n\n• Section 1\n• Section 2"
+        );
+    }
+
+    #[test]
+    fn caption_respects_limit() {
+        let caption = cover(200).caption(1024);
+        assert!(caption.chars().count() <= 1024);
+        assert!(caption.ends_with("\n…"));
+        assert!(caption.contains("• Section 50\n"));
+    }
+}
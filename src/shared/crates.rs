@@ -0,0 +1,289 @@
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use teloxide::utils::markdown::escape;
+
+use crate::parser::Section;
+
+/// Title of the TWIR section presenting the crate of the week.
+pub const CRATE_SECTION: &str = "Crate of the Week";
+
+/// Link prefixes that identify a crate by name.
+const CRATE_LINKS: [&str; 4] = [
+    "https://crates.io/crates/",
+    "https://docs.rs/",
+    "https://lib.rs/crates/",
+    "https://lib.rs/",
+];
+
+/// Settings for the Crate of the Week enrichment.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CrateIndexOptions {
+    /// Directory laid out like the crates.io index, e.g. a clone of
+    /// `crates.io-index`, files fetched from `index.crates.io` or the
+    /// `.cache` directory of a Cargo registry under `~/.cargo/registry/index`.
+    pub index: Option<PathBuf>,
+}
+
+/// One line of an index file describing a published version.
+#[derive(Debug, Clone, Deserialize)]
+struct IndexEntry {
+    vers: String,
+    #[serde(default)]
+    yanked: bool,
+    #[serde(default)]
+    license: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// Details of a crate found in the local index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateInfo {
+    pub name: String,
+    /// Latest version that is not yanked.
+    pub version: String,
+    pub license: Option<String>,
+    pub description: Option<String>,
+}
+
+impl CrateInfo {
+    /// Render the line appended to the Crate of the Week section, e.g.
+    /// `📦 ansic 0.3.1 · MIT · Compile-time ANSI strings`.
+    pub fn render(&self) -> String {
+        let mut parts = vec![format!("{} {}", self.name, self.version)];
+        parts.extend(self.license.clone());
+        parts.extend(
+            self.description
+                .as_deref()
+                .map(|d| d.split_whitespace().collect::<Vec<_>>().join(" ")),
+        );
+        format!("📦 {}", escape(&parts.join(" · ")))
+    }
+}
+
+/// Relative path of the index file for `name`, following the crates.io
+/// index layout (`1/a`, `2/ab`, `3/a/abc`, `an/si/ansic`); crate names are
+/// ASCII, so `None` for anything else.
+fn index_path(name: &str) -> Option<PathBuf> {
+    if !name.is_ascii() {
+        return None;
+    }
+    let name = name.to_ascii_lowercase();
+    let path = match name.len() {
+        0 => return None,
+        1 => Path::new("1").join(&name),
+        2 => Path::new("2").join(&name),
+        3 => Path::new("3").join(&name[..1]).join(&name),
+        _ => Path::new(&name[..2]).join(&name[2..4]).join(&name),
+    };
+    Some(path)
+}
+
+/// Sort key for a semantic version; pre-releases sort below every release,
+/// matching the version crates.io shows by default.
+fn version_key(version: &str) -> (bool, u64, u64, u64) {
+    let (core, pre) = match version.split_once('-') {
+        Some((core, _)) => (core, true),
+        None => (version.split('+').next().unwrap_or(version), false),
+    };
+    let mut numbers = core.split('.').map(|n| n.parse().unwrap_or(0));
+    (
+        !pre,
+        numbers.next().unwrap_or(0),
+        numbers.next().unwrap_or(0),
+        numbers.next().unwrap_or(0),
+    )
+}
+
+/// JSON records of an index file, either one per line as served by the index
+/// or NUL-separated in Cargo's binary `.cache` files, where they follow a
+/// header and alternate with version strings.
+fn index_records(bytes: &[u8]) -> Vec<String> {
+    if !bytes.contains(&0) {
+        return String::from_utf8_lossy(bytes)
+            .lines()
+            .map(str::to_string)
+            .collect();
+    }
+    bytes
+        .split(|&byte| byte == 0)
+        .filter(|record| record.starts_with(b"{"))
+        .map(|record| String::from_utf8_lossy(record).into_owned())
+        .collect()
+}
+
+/// Look up `name` in the index snapshot at `index`.
+///
+/// # Parameters
+/// - `index`: Root of the index snapshot.
+/// - `name`: Crate name; `-` and `_` are treated alike.
+///
+/// # Returns
+/// The latest non-yanked release, or `None` when the crate is unknown.
+pub fn lookup_crate(index: &Path, name: &str) -> Option<CrateInfo> {
+    let candidates = [
+        name.to_string(),
+        name.replace('_', "-"),
+        name.replace('-', "_"),
+    ];
+    let (name, bytes) = candidates.iter().find_map(|candidate| {
+        let path = index.join(index_path(candidate)?);
+        fs::read(path).ok().map(|bytes| (candidate, bytes))
+    })?;
+    let entry = index_records(&bytes)
+        .iter()
+        .filter_map(|line| match serde_json::from_str::<IndexEntry>(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                log::warn!("Skipping malformed index entry for {name}: {e}");
+                None
+            }
+        })
+        .filter(|entry| !entry.yanked)
+        .max_by_key(|entry| version_key(&entry.vers))?;
+    Some(CrateInfo {
+        name: name.clone(),
+        version: entry.vers,
+        license: entry.license,
+        description: entry.description,
+    })
+}
+
+/// Return the crate name linked from a rendered `line`, if any.
+fn linked_crate(line: &str) -> Option<&str> {
+    CRATE_LINKS.iter().find_map(|prefix| {
+        let start = line.find(&format!("]({prefix}"))? + 2 + prefix.len();
+        let name = line[start..]
+            .split(['/', ')', '?', '#'])
+            .next()
+            .unwrap_or_default();
+        (!name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .then_some(name)
+    })
+}
+
+/// Append the latest version, license and description of the featured
+/// crate below the line that links to it.
+///
+/// # Returns
+/// `true` when the crate was found in the index.
+pub(crate) fn enrich_crate_of_week(section: &mut Section, index: &Path) -> bool {
+    if !section.title.eq_ignore_ascii_case(CRATE_SECTION) {
+        return false;
+    }
+    let Some((line, info)) = section.lines.iter().enumerate().find_map(|(i, line)| {
+        let name = linked_crate(line)?;
+        match lookup_crate(index, name) {
+            Some(info) => Some((i, info)),
+            None => {
+                log::warn!("Crate {name} not found in index {}", index.display());
+                None
+            }
+        }
+    }) else {
+        return false;
+    };
+    section.replace_lines(line + 1..line + 1, vec![info.render()]);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sections;
+
+    fn write_index(dir: &Path, name: &str, lines: &[&str]) {
+        let path = dir.join(index_path(name).unwrap());
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, lines.join("\n")).unwrap();
+    }
+
+    #[test]
+    fn index_paths_follow_crates_io_layout() {
+        assert_eq!(index_path("a").unwrap(), Path::new("1/a"));
+        assert_eq!(index_path("ab").unwrap(), Path::new("2/ab"));
+        assert_eq!(index_path("Abc").unwrap(), Path::new("3/a/abc"));
+        assert_eq!(index_path("ansic").unwrap(), Path::new("an/si/ansic"));
+        assert_eq!(index_path("éa"), None);
+        assert_eq!(index_path("aéb"), None);
+    }
+
+    #[test]
+    fn non_ascii_crate_links_are_ignored() {
+        let line = "[éa](https://crates.io/crates/éa) is the crate";
+        assert_eq!(linked_crate(line), None);
+        assert!(lookup_crate(Path::new("."), "éa").is_none());
+    }
+
+    #[test]
+    fn picks_latest_release_that_is_not_yanked() {
+        let dir = tempfile::tempdir().unwrap();
+        write_index(
+            dir.path(),
+            "extend-mut",
+            &[
+                r#"{"name":"extend-mut","vers":"0.9.0","license":"MIT"}"#,
+                r#"{"name":"extend-mut","vers":"0.10.0","license":"MIT","description":"Extend\n lifetimes"}"#,
+                r#"{"name":"extend-mut","vers":"0.11.0-rc.1","license":"MIT"}"#,
+                r#"{"name":"extend-mut","vers":"0.12.0","yanked":true}"#,
+            ],
+        );
+        let info = lookup_crate(dir.path(), "extend_mut").unwrap();
+        assert_eq!(info.name, "extend-mut");
+        assert_eq!(info.version, "0.10.0");
+        assert_eq!(
+            info.render(),
+            "📦 extend\\-mut 0\\.10\\.0 · MIT · Extend lifetimes"
+        );
+        assert_eq!(lookup_crate(dir.path(), "missing"), None);
+    }
+
+    #[test]
+    fn reads_cargo_cache_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(index_path("ansic").unwrap());
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut cache = vec![3, 2, 0, 0, 0];
+        for record in [
+            "etag: \"abc\"",
+            "0.1.3",
+            r#"{"name":"ansic","vers":"0.1.3","license":"MIT"}"#,
+            "0.1.4",
+            r#"{"name":"ansic","vers":"0.1.4","license":"MIT OR Apache-2.0"}"#,
+        ] {
+            cache.extend(record.as_bytes());
+            cache.push(0);
+        }
+        fs::write(path, cache).unwrap();
+        let info = lookup_crate(dir.path(), "ansic").unwrap();
+        assert_eq!(info.version, "0.1.4");
+        assert_eq!(info.license.as_deref(), Some("MIT OR Apache-2.0"));
+    }
+
+    #[test]
+    fn info_follows_the_crate_link() {
+        let dir = tempfile::tempdir().unwrap();
+        write_index(
+            dir.path(),
+            "ansic",
+            &[r#"{"name":"ansic","vers":"0.1.4","license":"MIT OR Apache-2.0"}"#],
+        );
+        let mut section = parse_sections(
+            "## Crate of the Week\n\nThis week's crate is [ansic](https://crates.io/crates/ansic), a proc macro.\n\nThanks to [Zeon](https://users.rust-lang.org/t/1) for the suggestion!\n",
+        )
+        .remove(0);
+        assert!(enrich_crate_of_week(&mut section, dir.path()));
+        assert_eq!(
+            section.lines[1],
+            "📦 ansic 0\\.1\\.4 · MIT OR Apache\\-2\\.0"
+        );
+        assert!(section.lines[2].starts_with("Thanks to"));
+    }
+}
@@ -0,0 +1,354 @@
+use pulldown_cmark::{Event as MdEvent, HeadingLevel, Parser, Tag, TagEnd};
+use serde::Deserialize;
+use teloxide::utils::markdown::escape;
+
+use crate::generator::{escape_markdown_url, format_subheading};
+use crate::parser::{Section, Subheading};
+use crate::shared::locale::Messages;
+use crate::shared::urls::UrlOptions;
+
+/// Title of the TWIR section listing meetups and conferences.
+pub const EVENTS_SECTION: &str = "Upcoming Events";
+
+/// How people attend an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attendance {
+    InPerson,
+    Virtual,
+    Hybrid,
+}
+
+/// A link with its visible text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub text: String,
+    pub url: String,
+}
+
+/// A single meetup or conference from the "Upcoming Events" section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// Region sub-heading the event is listed under, e.g. `Europe`.
+    pub region: String,
+    /// First day in `YYYY-MM-DD` form.
+    pub start: String,
+    /// Last day of a multi-day event.
+    pub end: Option<String>,
+    /// Location as written in the issue, e.g. `Virtual (Berlin, DE)`.
+    pub location: String,
+    /// City part of the location without the attendance prefix.
+    pub city: String,
+    pub attendance: Attendance,
+    /// Organising groups.
+    pub groups: Vec<Link>,
+    /// Talk or meetup title.
+    pub title: String,
+    pub url: String,
+}
+
+impl Event {
+    /// Rewrite the event and group links with `urls`, like the links of the
+    /// parsed sections.
+    pub(crate) fn rewrite_urls(&mut self, urls: &UrlOptions) {
+        self.url = urls.rewrite(&self.url);
+        for group in &mut self.groups {
+            group.url = urls.rewrite(&group.url);
+        }
+    }
+}
+
+/// Selection of events published as a digest.
+///
+/// An event is kept when its region is listed in `regions` or its location
+/// contains one of `cities`. With both lists empty every event is kept.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EventDigest {
+    pub regions: Vec<String>,
+    pub cities: Vec<String>,
+    /// Publish the digest as a post of its own after the issue posts.
+    pub standalone: bool,
+}
+
+impl EventDigest {
+    pub(crate) fn matches(&self, event: &Event) -> bool {
+        if self.regions.is_empty() && self.cities.is_empty() {
+            return true;
+        }
+        let location = event.location.to_lowercase();
+        self.regions
+            .iter()
+            .any(|region| region.trim().eq_ignore_ascii_case(event.region.trim()))
+            || self
+                .cities
+                .iter()
+                .any(|city| location.contains(&city.trim().to_lowercase()))
+    }
+
+    /// Render the events matching this digest as a section.
+    ///
+    /// # Parameters
+    /// - `events`: Events parsed from the issue.
+    /// - `messages`: Catalog used for the attendance labels.
+    ///
+    /// # Returns
+    /// `None` when no event matches.
+    pub fn render(&self, events: &[Event], messages: &Messages) -> Option<Section> {
+        let mut section = Section {
+            title: EVENTS_SECTION.to_string(),
+            ..Section::default()
+        };
+        let mut region = None;
+        for event in events.iter().filter(|event| self.matches(event)) {
+            if region != Some(&event.region) {
+                region = Some(&event.region);
+                section.subheadings.push(Subheading {
+                    title: event.region.clone(),
+                    level: 3,
+                    line: section.lines.len(),
+                });
+                section.lines.push(format_subheading(&event.region));
+            }
+            section.lines.push(render_event(event, messages));
+        }
+        (!section.lines.is_empty()).then_some(section)
+    }
+}
+
+fn render_event(event: &Event, messages: &Messages) -> String {
+    let date = match &event.end {
+        Some(end) => format!("{}–{}", short_date(&event.start), short_date(end)),
+        None => short_date(&event.start).to_string(),
+    };
+    let place = match event.attendance {
+        Attendance::InPerson => event.city.clone(),
+        Attendance::Virtual if event.city.is_empty() => messages.online.to_string(),
+        Attendance::Virtual => format!("{}, {}", messages.online, event.city),
+        Attendance::Hybrid => format!("{}, {}", messages.hybrid, event.city),
+    };
+    let mut line = format!("• {}", escape(&date));
+    if !place.is_empty() {
+        line.push_str(&format!(" {}", escape(&place)));
+    }
+    line.push_str(&format!(
+        ": [{}]({})",
+        escape(&event.title),
+        escape_markdown_url(&event.url)
+    ));
+    line
+}
+
+/// Drop the year from a `YYYY-MM-DD` date.
+fn short_date(date: &str) -> &str {
+    date.split_once('-').map_or(date, |(_, rest)| rest)
+}
+
+fn split_location(location: &str) -> (Attendance, String) {
+    for (prefix, attendance) in [
+        ("Virtual", Attendance::Virtual),
+        ("Hybrid", Attendance::Hybrid),
+    ] {
+        if let Some(rest) = location.strip_prefix(prefix) {
+            let city = rest
+                .trim()
+                .trim_start_matches('(')
+                .trim_end_matches(')')
+                .trim();
+            return (attendance, city.to_string());
+        }
+    }
+    (Attendance::InPerson, location.to_string())
+}
+
+#[derive(Default)]
+struct Entry {
+    text: String,
+    groups: Vec<Link>,
+    talks: Vec<Link>,
+}
+
+impl Entry {
+    fn into_events(self, region: &str) -> Vec<Event> {
+        let mut fields = self.text.split('|').map(str::trim);
+        let dates = fields.next().unwrap_or_default();
+        let location = fields.next().unwrap_or_default().to_string();
+        let (start, end) = match dates.split_once(" - ") {
+            Some((start, end)) => (start.trim().to_string(), Some(end.trim().to_string())),
+            None => (dates.to_string(), None),
+        };
+        if start.is_empty() {
+            return Vec::new();
+        }
+        let (attendance, city) = split_location(&location);
+        let talks = if self.talks.is_empty() {
+            self.groups.iter().take(1).cloned().collect()
+        } else {
+            self.talks
+        };
+        talks
+            .into_iter()
+            .map(|talk| Event {
+                region: region.to_string(),
+                start: start.clone(),
+                end: end.clone(),
+                location: location.clone(),
+                city: city.clone(),
+                attendance,
+                groups: self.groups.clone(),
+                title: talk.text,
+                url: talk.url,
+            })
+            .collect()
+    }
+}
+
+/// Extract structured events from the "Upcoming Events" section of `markdown`.
+///
+/// Each `###` heading inside the section names a region. Entries have the
+/// form `* DATE | LOCATION | [Group](url)` with the talks as nested links.
+///
+/// # Parameters
+/// - `markdown`: Issue body without the metadata header.
+///
+/// # Returns
+/// Events in issue order.
+pub fn parse_events(markdown: &str) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut in_section = false;
+    let mut heading: Option<(HeadingLevel, String)> = None;
+    let mut region = String::new();
+    let mut depth = 0usize;
+    let mut entry: Option<Entry> = None;
+    let mut link: Option<Link> = None;
+
+    for md_event in Parser::new(markdown) {
+        match md_event {
+            MdEvent::Start(Tag::Heading { level, .. }) => {
+                heading = Some((level, String::new()));
+            }
+            MdEvent::End(TagEnd::Heading(_)) => {
+                let Some((level, text)) = heading.take() else {
+                    continue;
+                };
+                match level {
+                    HeadingLevel::H1 | HeadingLevel::H2 => {
+                        in_section = text.trim().eq_ignore_ascii_case(EVENTS_SECTION);
+                        region.clear();
+                    }
+                    _ if in_section => region = text.trim().to_string(),
+                    _ => {}
+                }
+            }
+            _ if !in_section && heading.is_none() => {}
+            MdEvent::Start(Tag::Item) => {
+                depth += 1;
+                if depth == 1 {
+                    entry = Some(Entry::default());
+                }
+            }
+            MdEvent::End(TagEnd::Item) => {
+                if depth == 1
+                    && let Some(done) = entry.take()
+                    && !region.is_empty()
+                {
+                    events.extend(done.into_events(&region));
+                }
+                depth = depth.saturating_sub(1);
+            }
+            MdEvent::Start(Tag::Link { dest_url, .. }) => {
+                link = Some(Link {
+                    text: String::new(),
+                    url: dest_url.to_string(),
+                });
+            }
+            MdEvent::End(TagEnd::Link) => {
+                if let (Some(done), Some(current)) = (link.take(), entry.as_mut()) {
+                    if depth == 1 {
+                        current.groups.push(done);
+                    } else {
+                        current.talks.push(done);
+                    }
+                }
+            }
+            MdEvent::Text(text) | MdEvent::Code(text) => {
+                if let Some((_, heading_text)) = heading.as_mut() {
+                    heading_text.push_str(&text);
+                } else if let Some(current) = link.as_mut() {
+                    current.text.push_str(&text);
+                }
+                if depth == 1
+                    && let Some(current) = entry.as_mut()
+                {
+                    current.text.push_str(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::locale::Locale;
+
+    const EVENTS: &str = "## Upcoming Events\n\nRusty Events between 2025-10-22 - 2025-11-19 🦀\n\n### Virtual\n* 2025-10-23 | Virtual (Berlin, DE) | [Rust Berlin](https://www.meetup.com/rust-berlin)\n    * [**Rust Hack and Learn**](https://www.meetup.com/rust-berlin/events/1/)\n* 2025-11-05 | Virtual | [Ardan Labs](https://example.com/ardan)\n    * [**Error Handling**](https://example.com/ardan/1)\n\n### Europe\n* 2025-11-02 - 2025-11-04 | Florence, IT | [Rustlab 2025](https://rustlab.it/)\n    * $[**Rustlab 2025**](https://rustlab.it/)\n* 2025-11-13 | Paris, FR | [Rust Paris](https://www.meetup.com/rust-paris/events/)\n    * [**Rust meetup #80**](https://www.meetup.com/rust-paris/events/2/)\n\n### North America\n* 2025-10-23 | Hybrid (Seattle/Bellevue, WA, US) | [Seattle Rust User Group](https://www.meetup.com/join-srug)\n    * [**SRUG Meetup**](https://www.meetup.com/seattle-rust-user-group/events/3/)\n\n## Jobs\n* [Not an event](https://example.com/)\n";
+
+    #[test]
+    fn parses_events_by_region() {
+        let events = parse_events(EVENTS);
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0].region, "Virtual");
+        assert_eq!(events[0].attendance, Attendance::Virtual);
+        assert_eq!(events[0].city, "Berlin, DE");
+        assert_eq!(events[0].title, "Rust Hack and Learn");
+        assert_eq!(events[0].groups[0].text, "Rust Berlin");
+        assert_eq!(events[1].city, "");
+
+        let rustlab = &events[2];
+        assert_eq!(rustlab.region, "Europe");
+        assert_eq!(rustlab.start, "2025-11-02");
+        assert_eq!(rustlab.end.as_deref(), Some("2025-11-04"));
+        assert_eq!(rustlab.attendance, Attendance::InPerson);
+        assert_eq!(rustlab.city, "Florence, IT");
+        assert_eq!(rustlab.url, "https://rustlab.it/");
+
+        assert_eq!(events[4].attendance, Attendance::Hybrid);
+    }
+
+    #[test]
+    fn digest_filters_by_region_and_city() {
+        let events = parse_events(EVENTS);
+        let digest = EventDigest {
+            regions: vec!["europe".into()],
+            cities: vec!["Berlin".into()],
+            standalone: false,
+        };
+        let section = digest.render(&events, Locale::En.messages()).unwrap();
+        assert_eq!(section.title, EVENTS_SECTION);
+        assert_eq!(
+            section.lines,
+            vec![
+                "**Virtual**",
+                "• 10\\-23 online, Berlin, DE: [Rust Hack and Learn](https://www.meetup.com/rust-berlin/events/1/)",
+                "**Europe**",
+                "• 11\\-02–11\\-04 Florence, IT: [Rustlab 2025](https://rustlab.it/)",
+                "• 11\\-13 Paris, FR: [Rust meetup \\#80](https://www.meetup.com/rust-paris/events/2/)",
+            ]
+        );
+    }
+
+    #[test]
+    fn digest_without_matches_is_empty() {
+        let digest = EventDigest {
+            regions: vec!["Oceania".into()],
+            ..EventDigest::default()
+        };
+        assert!(
+            digest
+                .render(&parse_events(EVENTS), Locale::En.messages())
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,322 @@
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use serde::Serialize;
+use std::{fs, io, path::Path};
+
+use crate::parser::Section;
+use crate::shared::tags::split_media_tags;
+
+/// File name of the JSON document written by `--format json`.
+pub const JSON_FILE: &str = "issue.json";
+
+/// Machine-readable representation of a whole TWIR issue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IssueDocument {
+    pub metadata: IssueInfo,
+    pub sections: Vec<SectionDocument>,
+}
+
+/// Issue metadata taken from the `Title:`, `Number:` and `Date:` headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IssueInfo {
+    pub number: Option<String>,
+    pub date: Option<String>,
+    pub title: Option<String>,
+    /// Link to the issue on this-week-in-rust.org.
+    pub url: Option<String>,
+}
+
+/// A `##` section of the issue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SectionDocument {
+    pub title: String,
+    /// Paragraphs and code blocks before the first subheading.
+    pub text: Vec<String>,
+    /// Links found in `text`, in order.
+    pub links: Vec<TextLink>,
+    /// List items before the first subheading.
+    pub items: Vec<Item>,
+    pub subsections: Vec<SubsectionDocument>,
+}
+
+/// Content following a subheading up to the next one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SubsectionDocument {
+    pub title: String,
+    /// Markdown heading level (1, 3 or 4).
+    pub level: u8,
+    pub text: Vec<String>,
+    /// Links found in `text`, in order.
+    pub links: Vec<TextLink>,
+    pub items: Vec<Item>,
+}
+
+/// A link inside a paragraph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TextLink {
+    pub title: String,
+    pub url: String,
+}
+
+/// A list item with the links and tags found in it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Item {
+    /// Item text without formatting or tags.
+    pub text: String,
+    /// Text of the first link that is not an author mention.
+    pub title: Option<String>,
+    pub url: Option<String>,
+    /// GitHub or users.rust-lang.org accounts mentioned in the item.
+    pub authors: Vec<String>,
+    /// Leading bracketed tags such as `audio` or `video`.
+    pub tags: Vec<String>,
+    /// Nested list items.
+    pub children: Vec<Item>,
+}
+
+#[derive(Default)]
+struct Block {
+    text: Vec<String>,
+    links: Vec<TextLink>,
+    items: Vec<Item>,
+}
+
+/// Return the account name when `url` points at a user profile.
+fn mention(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://github.com/")
+        .or_else(|| url.strip_prefix("https://users.rust-lang.org/u/"))?;
+    let name = rest.trim_end_matches('/');
+    (!name.is_empty() && !name.contains(['/', '?', '#'])).then_some(name)
+}
+
+/// Parse a rendered Telegram Markdown item line.
+fn parse_item(line: &str) -> Item {
+    let (tags, line) = split_media_tags(line);
+    let mut item = Item {
+        tags: tags.into_iter().map(|tag| tag.name).collect(),
+        ..Item::default()
+    };
+    let mut plain = String::new();
+    let mut link: Option<(String, String)> = None;
+    for event in Parser::new(line) {
+        match event {
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                link = Some((dest_url.to_string(), String::new()));
+            }
+            Event::End(TagEnd::Link) => {
+                let Some((url, text)) = link.take() else {
+                    continue;
+                };
+                if let Some(name) = mention(&url) {
+                    item.authors.push(name.to_string());
+                } else if item.url.is_none() {
+                    item.title = Some(text.trim().to_string());
+                    item.url = Some(url);
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                plain.push_str(&text);
+                if let Some((_, link_text)) = link.as_mut() {
+                    link_text.push_str(&text);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => plain.push(' '),
+            _ => {}
+        }
+    }
+    item.text = plain.trim().to_string();
+    item
+}
+
+/// Strip the formatting of a rendered paragraph line, collecting its links.
+fn plain_text(line: &str, links: &mut Vec<TextLink>) -> String {
+    let mut text = String::new();
+    let mut link: Option<(String, String)> = None;
+    for event in Parser::new(line) {
+        match event {
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                link = Some((dest_url.to_string(), String::new()));
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((url, title)) = link.take() {
+                    links.push(TextLink {
+                        title: title.trim().to_string(),
+                        url,
+                    });
+                }
+            }
+            Event::Text(t) | Event::Code(t) => {
+                text.push_str(&t);
+                if let Some((_, title)) = link.as_mut() {
+                    title.push_str(&t);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+/// Collect text and list items from rendered section `lines`.
+fn parse_block(lines: &[String]) -> Block {
+    let mut block = Block::default();
+    let mut code: Option<Vec<&str>> = None;
+    for line in lines {
+        if line.trim_start().starts_with("```") {
+            match code.take() {
+                Some(body) => block.text.push(body.join("\n")),
+                None => code = Some(Vec::new()),
+            }
+            continue;
+        }
+        if let Some(body) = code.as_mut() {
+            body.push(line);
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if let Some(rest) = line.trim_start().strip_prefix('•') {
+            let item = parse_item(rest.trim_start());
+            let depth = indent / 2;
+            let mut siblings = &mut block.items;
+            for _ in 0..depth {
+                if siblings.is_empty() {
+                    break;
+                }
+                siblings = &mut siblings.last_mut().expect("non-empty").children;
+            }
+            siblings.push(item);
+        } else {
+            let text = plain_text(line, &mut block.links);
+            if !text.is_empty() {
+                block.text.push(text);
+            }
+        }
+    }
+    if let Some(body) = code {
+        block.text.push(body.join("\n"));
+    }
+    block
+}
+
+/// Convert sections returned by
+/// [`parse_sections`](crate::parser::parse_sections) into a structured
+/// document.
+///
+/// # Parameters
+/// - `info`: Issue metadata.
+/// - `sections`: Parsed sections in issue order.
+///
+/// # Returns
+/// The issue document ready for serialization.
+pub fn build_document(info: IssueInfo, sections: &[Section]) -> IssueDocument {
+    let sections = sections
+        .iter()
+        .map(|section| {
+            let intro_end = section
+                .subheadings
+                .first()
+                .map_or(section.lines.len(), |sub| sub.line);
+            let intro = parse_block(&section.lines[..intro_end]);
+            let subsections = section
+                .subheadings
+                .iter()
+                .enumerate()
+                .map(|(index, sub)| {
+                    let end = section
+                        .subheadings
+                        .get(index + 1)
+                        .map_or(section.lines.len(), |next| next.line);
+                    let block = parse_block(&section.lines[(sub.line + 1).min(end)..end]);
+                    SubsectionDocument {
+                        title: sub.title.clone(),
+                        level: sub.level,
+                        text: block.text,
+                        links: block.links,
+                        items: block.items,
+                    }
+                })
+                .collect();
+            SectionDocument {
+                title: section.title.clone(),
+                text: intro.text,
+                links: intro.links,
+                items: intro.items,
+                subsections,
+            }
+        })
+        .collect();
+    IssueDocument {
+        metadata: info,
+        sections,
+    }
+}
+
+/// Write `document` as pretty-printed JSON to [`JSON_FILE`] in `dir`.
+///
+/// # Returns
+/// `Ok(())` on success or any serialization or file I/O error encountered.
+pub fn write_document(document: &IssueDocument, dir: &Path) -> io::Result<()> {
+    let mut json = serde_json::to_string_pretty(document).map_err(io::Error::other)?;
+    json.push('\n');
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(JSON_FILE), json)?;
+    println!("Generated {JSON_FILE}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sections;
+
+    #[test]
+    fn items_keep_links_tags_and_authors() {
+        let item = parse_item(
+            "\\[video\\] \\[ZH\\] [Rust \\(2024\\)](https://example.com/a_\\(b\\)) by [alice](https://github.com/alice)",
+        );
+        assert_eq!(item.tags, vec!["video", "zh"]);
+        assert_eq!(item.title.as_deref(), Some("Rust (2024)"));
+        assert_eq!(item.url.as_deref(), Some("https://example.com/a_(b)"));
+        assert_eq!(item.authors, vec!["alice"]);
+        assert_eq!(item.text, "Rust (2024) by alice");
+    }
+
+    #[test]
+    fn repository_links_are_not_mentions() {
+        assert_eq!(mention("https://github.com/alice"), Some("alice"));
+        assert_eq!(mention("https://github.com/rust-lang/rust/pull/1"), None);
+        assert_eq!(mention("https://users.rust-lang.org/u/bob"), Some("bob"));
+    }
+
+    #[test]
+    fn sections_are_grouped_by_subheading() {
+        let sections = parse_sections(
+            "## Updates\nIntro text.\n### Official\n* [A](https://a.example)\n    * [nested](https://n.example)\n### Videos\n* [video] [B](https://b.example)\n",
+        );
+        let document = build_document(IssueInfo::default(), &sections);
+        let section = &document.sections[0];
+        assert_eq!(section.text, vec!["Intro text."]);
+        assert_eq!(section.subsections.len(), 2);
+        let official = &section.subsections[0];
+        assert_eq!(official.title, "Official");
+        assert_eq!(official.items[0].url.as_deref(), Some("https://a.example"));
+        assert_eq!(
+            official.items[0].children[0].title.as_deref(),
+            Some("nested")
+        );
+        assert_eq!(section.subsections[1].items[0].tags, vec!["video"]);
+
+        let sections = parse_sections(
+            "## Crate of the Week\nThis week's crate is [ansic](https://docs.rs/ansic), a macro.\n",
+        );
+        let document = build_document(IssueInfo::default(), &sections);
+        assert_eq!(
+            document.sections[0].links,
+            vec![TextLink {
+                title: "ansic".into(),
+                url: "https://docs.rs/ansic".into()
+            }]
+        );
+    }
+}
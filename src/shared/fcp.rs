@@ -0,0 +1,463 @@
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+use serde::Deserialize;
+use teloxide::utils::markdown::escape;
+
+use crate::generator::escape_markdown_url;
+use crate::parser::Section;
+use crate::shared::locale::Messages;
+use crate::shared::urls::UrlOptions;
+
+/// Title of the TWIR subsection listing items in final comment period.
+pub const FCP_SECTION: &str = "Final Comment Period";
+
+/// Settings for the Final Comment Period transform.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FcpOptions {
+    /// Move FCP items out of the issue posts into a post of their own.
+    pub standalone: bool,
+}
+
+/// An RFC, PR or tracking issue in final comment period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FcpItem {
+    /// Team from the `#####` heading, e.g. `Leadership Council`.
+    pub team: String,
+    /// Link to the team's FCP list.
+    pub team_url: Option<String>,
+    /// Proposed outcome such as `merge` or `close`, when announced.
+    pub disposition: Option<String>,
+    pub title: String,
+    pub url: String,
+}
+
+impl FcpItem {
+    /// Rewrite the item and team links with `urls`, like the links of the
+    /// parsed sections.
+    pub(crate) fn rewrite_urls(&mut self, urls: &UrlOptions) {
+        self.url = urls.rewrite(&self.url);
+        if let Some(team_url) = &mut self.team_url {
+            *team_url = urls.rewrite(team_url);
+        }
+    }
+}
+
+/// A team named in the "No Items entered Final Comment Period this week for
+/// …" note of the issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuietTeam {
+    pub name: String,
+    /// Link to the team's FCP list.
+    pub url: String,
+}
+
+impl QuietTeam {
+    /// Rewrite the team link with `urls`, like the links of the parsed
+    /// sections.
+    pub(crate) fn rewrite_urls(&mut self, urls: &UrlOptions) {
+        self.url = urls.rewrite(&self.url);
+    }
+}
+
+/// Opening words of the note listing teams without FCP items.
+const QUIET_NOTE: &str = "no items entered final comment period";
+
+/// Badge shown in front of an item with the given disposition.
+fn disposition_badge(disposition: &str) -> &'static str {
+    match disposition {
+        "merge" => "✅",
+        "close" => "❌",
+        "postpone" => "⏸️",
+        _ => "❔",
+    }
+}
+
+/// Read a leading `[disposition: X]` marker from `text`.
+fn find_disposition(text: &str) -> Option<String> {
+    let rest = text.trim_start().strip_prefix("[disposition:")?;
+    let end = rest.find(']')?;
+    Some(rest[..end].trim().to_lowercase())
+}
+
+/// Extract FCP records from the "Final Comment Period" part of `markdown`.
+///
+/// Items are collected below `#####` team headings; any `####` or higher
+/// heading ends the current team.
+///
+/// # Parameters
+/// - `markdown`: Issue body without the metadata header.
+///
+/// # Returns
+/// The items in issue order.
+pub fn parse_fcp(markdown: &str) -> Vec<FcpItem> {
+    let mut items = Vec::new();
+    let mut in_section = false;
+    let mut heading: Option<(HeadingLevel, String, Option<String>)> = None;
+    let mut team: Option<(String, Option<String>)> = None;
+    let mut depth = 0usize;
+    let mut prefix = String::new();
+    let mut link: Option<(String, String)> = None;
+    let mut item: Option<FcpItem> = None;
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                heading = Some((level, String::new(), None));
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                let Some((level, text, url)) = heading.take() else {
+                    continue;
+                };
+                match level {
+                    HeadingLevel::H5 | HeadingLevel::H6 if in_section => {
+                        team = Some((text.trim().to_string(), url));
+                    }
+                    HeadingLevel::H4 => team = None,
+                    _ => {
+                        in_section = text.trim().eq_ignore_ascii_case(FCP_SECTION);
+                        team = None;
+                    }
+                }
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                if let Some((_, _, url)) = heading.as_mut() {
+                    url.get_or_insert_with(|| dest_url.to_string());
+                } else if team.is_some() && depth == 1 {
+                    link = Some((dest_url.to_string(), String::new()));
+                }
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((url, text)) = link.take()
+                    && item.is_none()
+                    && let Some((team, team_url)) = team.as_ref()
+                {
+                    item = Some(FcpItem {
+                        team: team.clone(),
+                        team_url: team_url.clone(),
+                        disposition: find_disposition(&prefix),
+                        title: text.trim().to_string(),
+                        url,
+                    });
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, heading_text, _)) = heading.as_mut() {
+                    heading_text.push_str(&text);
+                } else if let Some((_, link_text)) = link.as_mut() {
+                    link_text.push_str(&text);
+                } else if depth == 1 && item.is_none() {
+                    prefix.push_str(&text);
+                }
+            }
+            Event::Start(Tag::Item) => {
+                depth += 1;
+                if depth == 1 {
+                    prefix.clear();
+                    item = None;
+                }
+            }
+            Event::End(TagEnd::Item) => {
+                if depth == 1
+                    && let Some(done) = item.take()
+                {
+                    items.push(done);
+                }
+                depth = depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+    items
+}
+
+/// Extract the teams without FCP items from the "Final Comment Period" part
+/// of `markdown`.
+///
+/// # Parameters
+/// - `markdown`: Issue body without the metadata header.
+///
+/// # Returns
+/// The teams linked in the "No Items entered Final Comment Period" notes, in
+/// issue order.
+pub fn parse_quiet_teams(markdown: &str) -> Vec<QuietTeam> {
+    let mut teams = Vec::new();
+    let mut in_section = false;
+    let mut heading: Option<String> = None;
+    let mut paragraph: Option<(String, Vec<QuietTeam>)> = None;
+    let mut link: Option<(String, String)> = None;
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Heading { .. }) => heading = Some(String::new()),
+            Event::End(TagEnd::Heading(level)) => {
+                let text = heading.take().unwrap_or_default();
+                if matches!(
+                    level,
+                    HeadingLevel::H1 | HeadingLevel::H2 | HeadingLevel::H3
+                ) {
+                    in_section = text.trim().eq_ignore_ascii_case(FCP_SECTION);
+                }
+            }
+            Event::Start(Tag::Paragraph) if in_section => {
+                paragraph = Some((String::new(), Vec::new()));
+            }
+            Event::End(TagEnd::Paragraph) => {
+                if let Some((text, found)) = paragraph.take()
+                    && text.trim_start().to_lowercase().starts_with(QUIET_NOTE)
+                {
+                    teams.extend(found);
+                }
+            }
+            Event::Start(Tag::Link { dest_url, .. }) if paragraph.is_some() => {
+                link = Some((dest_url.to_string(), String::new()));
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((url, name)) = link.take()
+                    && let Some((_, found)) = paragraph.as_mut()
+                {
+                    found.push(QuietTeam {
+                        name: name.trim().to_string(),
+                        url,
+                    });
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(heading_text) = heading.as_mut() {
+                    heading_text.push_str(&text);
+                } else if let Some((_, name)) = link.as_mut() {
+                    name.push_str(&text);
+                } else if let Some((paragraph_text, _)) = paragraph.as_mut() {
+                    paragraph_text.push_str(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+    teams
+}
+
+/// Render the teams without FCP items as a single line introduced in the
+/// language of `messages`.
+///
+/// # Returns
+/// `None` when `teams` is empty.
+pub fn render_quiet_teams(teams: &[QuietTeam], messages: &Messages) -> Option<String> {
+    if teams.is_empty() {
+        return None;
+    }
+    let links: Vec<String> = teams
+        .iter()
+        .map(|team| {
+            format!(
+                "[{}]({})",
+                escape(&team.name),
+                escape_markdown_url(&team.url)
+            )
+        })
+        .collect();
+    Some(format!(
+        "_{}_ {}",
+        escape(messages.fcp_quiet),
+        links.join(", ")
+    ))
+}
+
+/// Render `items` grouped by team with disposition badges.
+pub fn render_fcp(items: &[FcpItem]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut team: Option<&str> = None;
+    for item in items {
+        if team != Some(item.team.as_str()) {
+            team = Some(&item.team);
+            lines.push(match &item.team_url {
+                Some(url) => format!("**[{}]({})**", escape(&item.team), escape_markdown_url(url)),
+                None => format!("**{}**", escape(&item.team)),
+            });
+        }
+        let mut line = String::from("• ");
+        if let Some(disposition) = &item.disposition {
+            line.push_str(&format!(
+                "{} _{}_ ",
+                disposition_badge(disposition),
+                escape(disposition)
+            ));
+        }
+        line.push_str(&format!(
+            "[{}]({})",
+            escape(&item.title),
+            escape_markdown_url(&item.url)
+        ));
+        lines.push(line);
+    }
+    lines
+}
+
+/// Section published when FCP items form a post of their own, introduced in
+/// the language of `messages` and closed by the teams without items.
+pub fn standalone_fcp_section(
+    items: &[FcpItem],
+    quiet: &[QuietTeam],
+    messages: &Messages,
+) -> Option<Section> {
+    if items.is_empty() {
+        return None;
+    }
+    let mut lines = vec![escape(messages.fcp_intro), String::new()];
+    lines.extend(render_fcp(items));
+    lines.extend(render_quiet_teams(quiet, messages));
+    Some(Section {
+        title: FCP_SECTION.to_string(),
+        lines,
+        ..Section::default()
+    })
+}
+
+/// Replace the FCP listing inside `section` with `replacement`.
+///
+/// Items are found by their links, so `items` must carry the links as
+/// rewritten in the section, see [`FcpItem::rewrite_urls`].
+///
+/// The replaced range starts after the "Final Comment Period" heading, or at
+/// the top of a section promoted from it, and ends before the first
+/// subheading following the last line that links to one of `items`. Content
+/// after it, such as "New and Updated RFCs", is kept. With `replacement` set
+/// to `None` the heading is removed too.
+///
+/// # Returns
+/// `true` when the section contained an FCP listing.
+pub(crate) fn replace_fcp_listing(
+    section: &mut Section,
+    items: &[FcpItem],
+    replacement: Option<Vec<String>>,
+) -> bool {
+    let (heading, scope) = if section.title.eq_ignore_ascii_case(FCP_SECTION) {
+        (None, 0..section.lines.len())
+    } else if let Some(index) = section
+        .subheadings
+        .iter()
+        .position(|sub| sub.title.eq_ignore_ascii_case(FCP_SECTION))
+    {
+        (
+            Some(section.subheadings[index].line),
+            section.subsection_range(index),
+        )
+    } else {
+        return false;
+    };
+
+    let targets: Vec<String> = items
+        .iter()
+        .map(|item| format!("]({})", escape_markdown_url(&item.url)))
+        .collect();
+    let Some(last_item) = scope
+        .clone()
+        .rev()
+        .find(|&idx| targets.iter().any(|t| section.lines[idx].contains(t)))
+    else {
+        return false;
+    };
+    let end = section
+        .subheadings
+        .iter()
+        .map(|sub| sub.line)
+        .find(|&line| line > last_item && line < scope.end)
+        .unwrap_or(scope.end);
+
+    match replacement {
+        Some(lines) => section.replace_lines(scope.start..end, lines),
+        None => section.replace_lines(heading.unwrap_or(scope.start)..end, Vec::new()),
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sections;
+
+    const ISSUE: &str = "## Updates from the Rust Project\n### Approved RFCs\n* none\n### Final Comment Period\n\nEvery week, the team announces things.\n\n#### Tracking Issues & PRs\n##### [Rust](https://github.com/rust-lang/rust/issues)\n* [disposition: merge] [Stabilize `foo`](https://github.com/rust-lang/rust/pull/1)\n* [Tracking Issue for `bar`](https://github.com/rust-lang/rust/issues/2)\n\n##### [Compiler Team](https://github.com/rust-lang/compiler-team/issues) [(MCPs only)](https://forge.rust-lang.org/compiler/mcp.html)\n* [disposition: close] [[std][BTree] Fix](https://github.com/rust-lang/compiler-team/issues/3)\n\n*No Items entered Final Comment Period this week for [Cargo](https://example.com).*\n\n#### [New and Updated RFCs](https://github.com/rust-lang/rfcs/pulls)\n* [RFC](https://github.com/rust-lang/rfcs/pull/4)\n";
+
+    #[test]
+    fn parses_items_with_team_and_disposition() {
+        let items = parse_fcp(ISSUE);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].team, "Rust");
+        assert_eq!(items[0].disposition.as_deref(), Some("merge"));
+        assert_eq!(items[0].title, "Stabilize foo");
+        assert_eq!(items[1].disposition, None);
+        assert_eq!(items[2].team, "Compiler Team (MCPs only)");
+        assert_eq!(
+            items[2].team_url.as_deref(),
+            Some("https://github.com/rust-lang/compiler-team/issues")
+        );
+        assert_eq!(items[2].title, "[std][BTree] Fix");
+    }
+
+    #[test]
+    fn renders_groups_with_badges() {
+        let lines = render_fcp(&parse_fcp(ISSUE));
+        assert_eq!(
+            lines,
+            vec![
+                "**[Rust](https://github.com/rust-lang/rust/issues)**",
+                "• ✅ _merge_ [Stabilize foo](https://github.com/rust-lang/rust/pull/1)",
+                "• [Tracking Issue for bar](https://github.com/rust-lang/rust/issues/2)",
+                "**[Compiler Team \\(MCPs only\\)](https://github.com/rust-lang/compiler-team/issues)**",
+                "• ❌ _close_ [\\[std\\]\\[BTree\\] Fix](https://github.com/rust-lang/compiler-team/issues/3)",
+            ]
+        );
+    }
+
+    #[test]
+    fn quiet_teams_are_summarised() {
+        let teams = parse_quiet_teams(ISSUE);
+        assert_eq!(
+            teams,
+            vec![QuietTeam {
+                name: "Cargo".into(),
+                url: "https://example.com".into()
+            }]
+        );
+        assert!(parse_quiet_teams("## Other\n*No Items entered Final Comment Period this week for [Cargo](https://example.com).*\n").is_empty());
+        let en = crate::shared::locale::Locale::En.messages();
+        assert_eq!(
+            render_quiet_teams(&teams, en).as_deref(),
+            Some("_No new items this week:_ [Cargo](https://example.com)")
+        );
+        let section = standalone_fcp_section(&parse_fcp(ISSUE), &teams, en).unwrap();
+        assert_eq!(
+            section.lines.last().map(String::as_str),
+            Some("_No new items this week:_ [Cargo](https://example.com)")
+        );
+        assert!(render_quiet_teams(&[], en).is_none());
+    }
+
+    #[test]
+    fn listing_is_replaced_up_to_next_subsection() {
+        let items = parse_fcp(ISSUE);
+        let mut section = parse_sections(ISSUE).remove(0);
+        assert!(replace_fcp_listing(
+            &mut section,
+            &items,
+            Some(render_fcp(&items))
+        ));
+        let fcp = section
+            .subheadings
+            .iter()
+            .position(|sub| sub.title == FCP_SECTION)
+            .unwrap();
+        assert_eq!(
+            section.lines[section.subheadings[fcp].line + 1],
+            "**[Rust](https://github.com/rust-lang/rust/issues)**"
+        );
+        assert!(!section.lines.iter().any(|l| l.contains("No Items")));
+        let rfcs = section.subheadings.last().unwrap();
+        assert_eq!(rfcs.title, "New and Updated RFCs");
+        assert!(section.lines[rfcs.line].contains("New and Updated RFCs"));
+
+        let mut section = parse_sections(ISSUE).remove(0);
+        assert!(replace_fcp_listing(&mut section, &items, None));
+        assert!(!section.lines.iter().any(|l| l.contains("Final Comment")));
+        assert!(section.lines.iter().any(|l| l.contains("New and Updated")));
+    }
+}
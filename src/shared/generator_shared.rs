@@ -1,15 +1,74 @@
 use log::{debug, error, info, warn};
 use phf::phf_map;
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, multipart};
 use serde::Deserialize;
 use std::{borrow::Cow, fs, path::Path, thread, time::Duration};
 use teloxide::utils::markdown::escape;
 
-use crate::parser::{Section, parse_sections};
+use crate::parser::{ParseReport, Section, parse_sections_report, parse_sections_with};
+use crate::shared::calendar::events_to_ical;
+use crate::shared::crates::enrich_crate_of_week;
+use crate::shared::events::{EVENTS_SECTION, parse_events};
+use crate::shared::export::build_document;
+use crate::shared::fcp::{
+    FCP_SECTION, parse_fcp, parse_quiet_teams, render_fcp, render_quiet_teams, replace_fcp_listing,
+    standalone_fcp_section,
+};
+use crate::shared::packing::{keep_footer_attached, pack_balanced};
+use crate::shared::perf::{parse_perf_triage, replace_perf_triage};
+use crate::shared::pulls::{apply_pull_digest, parse_pull_requests};
+use crate::shared::rules::{apply_rules, rule_changes};
+use crate::shared::sections::apply_section_policy;
+use crate::shared::tags::apply_media_tags;
+use crate::shared::theme::{DEFAULT_THEME, apply_theme};
+use crate::shared::toc::locate_headings;
+use crate::shared::translate::translate_section;
 use crate::validator::validate_telegram_markdown;
 
+pub use crate::shared::calendar::{CALENDAR_FILE, write_calendar};
+pub use crate::shared::card::{CardOptions, PhotoPost, write_photo};
+pub use crate::shared::config::{GenerationOptions, load_options, parse_options};
+pub use crate::shared::cover::{COVER_FILE, Cover, CoverOptions, render_cover};
+pub use crate::shared::crates::{CrateIndexOptions, CrateInfo, lookup_crate};
+pub use crate::shared::events::{Attendance, Event, EventDigest, Link};
+pub use crate::shared::export::{
+    IssueDocument, IssueInfo, Item, JSON_FILE, SectionDocument, SubsectionDocument, TextLink,
+    write_document,
+};
+pub use crate::shared::fcp::{FcpItem, FcpOptions, QuietTeam};
+pub use crate::shared::glossary::Glossary;
+pub use crate::shared::jobs::{JobPosting, JobResource, JobsOptions, parse_reddit_thread};
+pub use crate::shared::links::{
+    LinkProblem, LinkReport, LinkStatus, PostLink, Severity, TRACKING_PARAMS, check_links_online,
+    extract_links, is_tracking_param, lint_links, rebase_url,
+};
+pub use crate::shared::locale::{Locale, Messages};
+pub use crate::shared::packing::PackingStrategy;
+pub use crate::shared::perf::{PerfOverall, PerfTriage};
+pub use crate::shared::pulls::{PullCategory, PullOptions, PullRequest, PullRequestStats};
+pub use crate::shared::quote::{
+    QUOTE_CARD_FILE, QUOTE_SECTION, Quote, QuoteOptions, parse_quote, render_quote_card,
+};
+pub use crate::shared::rules::{Rule, RuleAction, RuleChange, load_rules};
+pub use crate::shared::sections::{SectionPolicy, TruncateRule};
+pub use crate::shared::tags::{MEDIA_TAG_EMOJIS, MediaTag, TagOptions, split_media_tags};
+pub use crate::shared::theme::{HeadingCase, HeadingStyle, SECTION_EMOJIS, Theme, load_theme};
+pub use crate::shared::threading::{ReplyMode, ThreadingOptions};
+pub use crate::shared::toc::{TocEntry, TocOptions, message_link, render_toc};
+pub use crate::shared::transforms::{
+    CFP_TRANSFORM, JOBS_TRANSFORM, QUOTE_TRANSFORM, SectionTransform, TransformOptions,
+    TransformRegistry,
+};
+pub use crate::shared::translate::{
+    HttpTranslator, TRANSLATE_API_KEY_ENV, TranslateOptions, TranslationMemory, Translator,
+    translate_line,
+};
+pub use crate::shared::urls::{RewriteRule, UrlOptions};
+
 pub const TELEGRAM_LIMIT: usize = 4000;
 pub const TELEGRAM_DELAY_MS: u64 = 1000;
+/// Maximum length of a photo caption after entity parsing.
+pub const TELEGRAM_CAPTION_LIMIT: usize = 1024;
 /// Delay before attempting to pin the first message.
 pub const TELEGRAM_PIN_DELAY_MS: u64 = 2000;
 
@@ -29,129 +88,6 @@ pub static SUBHEADING_EMOJIS: phf::Map<&'static str, &'static str> = phf_map! {
     "tracking issues & prs" => "📌",
 };
 
-/// Short URL guiding contributors how to submit CFP tasks.
-const CFP_GUIDELINES: &str =
-    "https://github.com/rust-lang/this-week-in-rust#call-for-participation-guidelines";
-
-fn simplify_cfp_section(section: &mut Section) {
-    let mut cleaned = Vec::new();
-    let mut in_projects = false;
-    let mut has_task = false;
-
-    for line in section.lines.iter() {
-        if line.starts_with("**CFP \\- Projects**") {
-            in_projects = true;
-            cleaned.push(line.clone());
-            continue;
-        }
-        if line.starts_with("**CFP \\- Events**") {
-            in_projects = false;
-            cleaned.push(line.clone());
-            continue;
-        }
-        if line.contains("guidelines") && line.contains("submit tasks") {
-            continue;
-        }
-        if line.starts_with("Always wanted to contribute")
-            || line.starts_with("Some of these tasks")
-            || line.starts_with("Are you a new or experienced speaker")
-        {
-            continue;
-        }
-        if in_projects {
-            if line.trim() == "No Calls for participation were submitted this week." {
-                continue;
-            }
-            let trimmed = line.trim_start();
-            if trimmed.starts_with('•') || trimmed.starts_with('*') {
-                has_task = true;
-            }
-            cleaned.push(line.clone());
-        } else {
-            cleaned.push(line.clone());
-        }
-    }
-
-    if !has_task {
-        let msg = format!(
-            "No new tasks this week\\. [Instructions]({})",
-            escape_markdown_url(CFP_GUIDELINES)
-        );
-        cleaned.push(msg);
-    }
-
-    section.lines = cleaned;
-}
-
-fn simplify_quote_section(section: &mut Section) {
-    let mut cleaned = Vec::new();
-    let mut in_quote_section = false;
-    for line in &section.lines {
-        if line.contains("Quote of the Week") {
-            cleaned.push(format_subheading("Quote of the Week"));
-            in_quote_section = true;
-            continue;
-        }
-        if !in_quote_section {
-            cleaned.push(line.clone());
-            continue;
-        }
-
-        let trimmed = line.trim_start();
-        if trimmed.is_empty() {
-            if cleaned.last().is_some_and(|l| !l.is_empty()) {
-                cleaned.push(String::new());
-            }
-            continue;
-        }
-
-        let lower = trimmed.to_ascii_lowercase();
-        if lower.contains("please submit quotes")
-            || lower.starts_with("thanks to")
-            || lower.starts_with("despite ")
-        {
-            continue;
-        }
-
-        if trimmed.starts_with('–') {
-            cleaned.push(trimmed.to_string());
-            cleaned.push(String::new());
-            continue;
-        }
-
-        let content = trimmed
-            .trim_start_matches("\\>")
-            .trim_start_matches('>')
-            .trim_start();
-        if content.len() != trimmed.len() {
-            if !content.is_empty() {
-                cleaned.push(format!("_{content}_"));
-            } else if cleaned.last().is_some_and(|l| !l.is_empty()) {
-                cleaned.push(String::new());
-            }
-            continue;
-        }
-
-        cleaned.push(line.clone());
-    }
-    section.lines = cleaned;
-}
-
-fn simplify_jobs_section(section: &mut Section) {
-    const PAT: &str = "Hiring thread on r/rust](";
-    for line in &mut section.lines {
-        if let Some(start) = line.find(PAT) {
-            let url_start = start + PAT.len();
-            if let Some(rest) = line.get(url_start..)
-                && let Some(end) = rest.find(')')
-            {
-                let url = &rest[..end];
-                *line = format!("🦀 [Rust Job Reddit Thread]({})", escape_markdown_url(url));
-            }
-        }
-    }
-}
-
 fn find_value(text: &str, prefix: &str) -> Option<String> {
     for line in text.lines() {
         if let Some(rest) = line.strip_prefix(prefix) {
@@ -169,9 +105,14 @@ fn find_date(text: &str) -> Option<String> {
     find_value(text, "Date: ")
 }
 
+fn find_title(text: &str) -> Option<String> {
+    find_value(text, "Title: ")
+}
+
 struct IssueMetadata {
     number: Option<String>,
     date: Option<String>,
+    title: Option<String>,
     url: Option<String>,
 }
 
@@ -179,12 +120,18 @@ impl IssueMetadata {
     fn from_input(text: &str) -> Self {
         let number = find_number(text);
         let date = find_date(text);
+        let title = find_title(text);
         let url = match (date.as_deref(), number.as_deref()) {
             (Some(date), Some(number)) => build_issue_url(date, number),
             _ => None,
         };
 
-        Self { number, date, url }
+        Self {
+            number,
+            date,
+            title,
+            url,
+        }
     }
 
     fn header(&self) -> String {
@@ -245,13 +192,7 @@ pub fn escape_markdown_url(url: &str) -> String {
 /// # Returns
 /// A bold heading prefixed with an appropriate emoji.
 pub fn format_heading(title: &str) -> String {
-    let upper = title.to_uppercase();
-    let emoji = match upper.as_str() {
-        "UPCOMING EVENTS" => "🎉",
-        "CRATE OF THE WEEK" => "📦",
-        _ => "📰",
-    };
-    format!("{e} **{}** {e}", escape(&upper), e = emoji)
+    DEFAULT_THEME.heading(title)
 }
 
 /// Format a level 3 or level 4 heading.
@@ -262,24 +203,7 @@ pub fn format_heading(title: &str) -> String {
 /// # Returns
 /// The escaped heading wrapped in bold markers.
 pub fn format_subheading(title: &str) -> String {
-    let trimmed = title.trim();
-    if trimmed.starts_with('[')
-        && trimmed.ends_with(')')
-        && let Some(idx) = trimmed.find("](")
-    {
-        let text = &trimmed[1..idx];
-        let url = &trimmed[idx + 2..trimmed.len() - 1];
-        return format!("**[{}]({})**", escape(text), escape_markdown_url(url));
-    }
-    let lower = trimmed.to_ascii_lowercase();
-    if lower == "quote of the week" {
-        return format!("\n**{}:** 💬\n", escape(trimmed));
-    }
-    if let Some(emoji) = SUBHEADING_EMOJIS.get(lower.as_str()) {
-        format!("\n**{}:** {}", escape(trimmed), emoji)
-    } else {
-        format!("**{}**", escape(trimmed))
-    }
+    DEFAULT_THEME.subheading(title)
 }
 
 /// Convert Telegram Markdown to a plain text representation.
@@ -346,16 +270,163 @@ impl std::fmt::Display for ValidationError {
 
 impl std::error::Error for ValidationError {}
 
+/// Telegram posts of a TWIR issue with the titles of the sections they hold.
+#[derive(Debug, Clone, Default)]
+pub struct GeneratedIssue {
+    /// Validated Telegram Markdown posts.
+    pub posts: Vec<String>,
+    /// Untranslated titles of the issue sections in post order; untitled
+    /// sections such as the web version link have an empty title.
+    pub sections: Vec<String>,
+    /// Titles of the standalone Upcoming Events, Final Comment Period and
+    /// quote posts sent after the issue sections.
+    pub standalone: Vec<String>,
+}
+
+/// Preference for breaking a post before a given line.
+///
+/// Higher values are tried first when a post overflows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum BreakPriority {
+    /// Inside a fenced code block; only used when nothing better exists.
+    Anywhere,
+    /// Between two ordinary lines such as list items.
+    Line,
+    /// Before a bold subheading.
+    Subheading,
+    /// After a blank line separating sections or subsections.
+    Section,
+}
+
+/// A rendered line together with the context needed to break before it.
+struct SplitLine<'a> {
+    text: &'a str,
+    priority: BreakPriority,
+    /// Whether a fenced code block is still open after this line.
+    code_open_after: bool,
+    /// Whether the line is a bold heading that must not end a post.
+    heading: bool,
+}
+
+fn needs_escape(c: char) -> bool {
+    matches!(c, '-' | '>' | '#' | '+' | '=' | '{' | '}' | '.' | '!')
+}
+
+/// Returns `true` if `text` ends with a backslash that escapes nothing.
+fn has_dangling_backslash(text: &str) -> bool {
+    text.chars().rev().take_while(|&c| c == '\\').count() % 2 == 1
+}
+
+fn classify_lines(text: &str) -> Vec<SplitLine<'_>> {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+    let mut previous_blank = false;
+    for line in text.lines() {
+        let is_fence = line.trim() == "```";
+        let heading = !in_code_block && line.starts_with("**");
+        let priority = if in_code_block {
+            BreakPriority::Anywhere
+        } else if previous_blank {
+            BreakPriority::Section
+        } else if line.starts_with("**") {
+            BreakPriority::Subheading
+        } else {
+            BreakPriority::Line
+        };
+        if is_fence {
+            in_code_block = !in_code_block;
+        }
+        previous_blank = !in_code_block && line.trim().is_empty();
+        lines.push(SplitLine {
+            text: line,
+            priority,
+            code_open_after: in_code_block,
+            heading,
+        });
+    }
+    lines
+}
+
+/// Render a run of lines as a standalone post.
+///
+/// Blank lines at the edges are dropped, an open code block is closed or
+/// reopened as needed and a leading Markdown control character is escaped.
+fn render_chunk(lines: &[SplitLine<'_>], reopen_code: bool) -> String {
+    let start = lines
+        .iter()
+        .position(|line| !line.text.trim().is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|line| !line.text.trim().is_empty())
+        .map_or(start, |idx| idx + 1);
+    let lines = &lines[start..end.max(start)];
+
+    let mut out = String::new();
+    if reopen_code {
+        out.push_str("```\n");
+    } else if let Some(first) = lines.first()
+        && let Some(c) = first.text.chars().next()
+        && needs_escape(c)
+    {
+        out.push('\\');
+    }
+    for (idx, line) in lines.iter().enumerate() {
+        if idx > 0 {
+            out.push('\n');
+        }
+        out.push_str(line.text);
+    }
+    let code_open = lines
+        .last()
+        .map_or(reopen_code, |line| line.code_open_after);
+    if code_open {
+        out.push_str("\n```");
+    } else if has_dangling_backslash(&out) {
+        out.pop();
+    }
+    out
+}
+
+/// Choose where to break `pending` so that the head fits into `limit`.
+///
+/// The break with the highest [`BreakPriority`] that keeps the head at least
+/// half full wins; later breaks win ties. When no such break exists the
+/// latest one that fits is used. Breaks right after a heading line are only
+/// taken when nothing else fits, so a heading stays with its first child.
+fn choose_line_break(pending: &[SplitLine<'_>], reopen_code: bool, limit: usize) -> Option<usize> {
+    let mut best: Option<(BreakPriority, usize)> = None;
+    let mut fallback = None;
+    let mut last_resort = None;
+    for idx in 1..pending.len() {
+        let len = render_chunk(&pending[..idx], reopen_code).len();
+        if len > limit {
+            break;
+        }
+        last_resort = Some(idx);
+        if pending[idx - 1].heading {
+            continue;
+        }
+        fallback = Some(idx);
+        if len * 2 >= limit && best.is_none_or(|(priority, _)| pending[idx].priority >= priority) {
+            best = Some((pending[idx].priority, idx));
+        }
+    }
+    best.map(|(_, idx)| idx).or(fallback).or(last_resort)
+}
+
 /// Split a long message into chunks that obey Telegram's length limit.
 ///
-/// The function walks through the input line by line and builds a vector of
-/// posts whose length never exceeds `limit`. Lines longer than the limit are
-/// split character by character. When a chunk ends with a backslash the
-/// character is moved to the beginning of the next chunk so that escape
-/// sequences remain valid. If the start of a new post would begin with a
-/// Markdown control character, it is prefixed with a backslash to keep the
-/// formatting intact. Newlines are inserted between lines unless a trailing
-/// backslash caused the next line to be joined.
+/// The function walks through the input line by line and prefers to break
+/// between sections, then before subheadings, then between list items or
+/// paragraphs. Fenced code blocks are only broken when they do not fit into a
+/// single post, in which case the fence is closed and reopened; a code line
+/// longer than the limit is divided by characters into fenced chunks. Other
+/// lines longer than the limit are divided on sentence boundaries, then on spaces, and
+/// never inside an escape sequence or a `[text](url)` link; bold, italic and
+/// other entities open at the break are closed at the end of one chunk and
+/// reopened at the start of the next. If a post would begin with a Markdown
+/// control character, it is prefixed with a backslash.
 ///
 /// # Parameters
 /// - `text`: The text to split.
@@ -364,164 +435,513 @@ impl std::error::Error for ValidationError {}
 /// # Returns
 /// A vector of strings each no longer than `limit` characters.
 pub fn split_posts(text: &str, limit: usize) -> Vec<String> {
+    split_posts_with_glossary(text, limit, &Glossary::default())
+}
+
+/// Split a long message like [`split_posts`], never dividing inline code or
+/// a term of `glossary`.
+///
+/// # Parameters
+/// - `text`: The text to split.
+/// - `limit`: Maximum allowed length of each chunk.
+/// - `glossary`: Protected terms, as written before escaping.
+///
+/// # Returns
+/// A vector of strings each no longer than `limit` characters.
+pub fn split_posts_with_glossary(text: &str, limit: usize, glossary: &Glossary) -> Vec<String> {
+    let glossary = glossary.escaped();
     let mut posts = Vec::new();
+    let mut pending: Vec<SplitLine<'_>> = Vec::new();
+    let mut reopen_code = false;
+
+    for line in classify_lines(text) {
+        if line.text.len() > limit {
+            if !pending.is_empty() {
+                posts.push(render_chunk(&pending, reopen_code));
+                pending.clear();
+            }
+            posts.extend(split_overlong_line(&line, limit, &glossary));
+            reopen_code = line.code_open_after;
+            continue;
+        }
+
+        pending.push(line);
+        while render_chunk(&pending, reopen_code).len() > limit {
+            let Some(idx) = choose_line_break(&pending, reopen_code, limit) else {
+                let line = pending.remove(0);
+                posts.extend(split_overlong_line(&line, limit, &glossary));
+                reopen_code = line.code_open_after;
+                continue;
+            };
+            posts.push(render_chunk(&pending[..idx], reopen_code));
+            reopen_code = pending[idx - 1].code_open_after;
+            pending.drain(..idx);
+        }
+    }
+
+    if !pending.is_empty() {
+        posts.push(render_chunk(&pending, reopen_code));
+    }
+
+    posts.retain(|post| !post.is_empty());
+    posts
+}
+
+/// Split a line that does not fit into a post on its own.
+///
+/// Lines inside a fenced code block are divided by characters with every
+/// chunk wrapped in a fence; other lines go through [`split_long_line`].
+fn split_overlong_line(line: &SplitLine<'_>, limit: usize, glossary: &Glossary) -> Vec<String> {
+    if line.code_open_after && line.text.trim() != "```" {
+        split_code_line(line.text, limit)
+    } else {
+        split_long_line(line.text, limit, glossary)
+    }
+}
+
+/// Divide a code block line into fenced chunks, keeping escape sequences
+/// whole.
+fn split_code_line(line: &str, limit: usize) -> Vec<String> {
+    let budget = limit.saturating_sub("```\n\n```".len()).max(2);
+    let mut pieces = Vec::new();
     let mut current = String::new();
-    let mut join_next = false;
-    let mut in_code_block = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        let mut unit = String::from(c);
+        if c == '\\'
+            && let Some(next) = chars.next()
+        {
+            unit.push(next);
+        }
+        if !current.is_empty() && current.len() + unit.len() > budget {
+            pieces.push(format!("```\n{current}\n```"));
+            current.clear();
+        }
+        current.push_str(&unit);
+    }
+    if !current.is_empty() {
+        pieces.push(format!("```\n{current}\n```"));
+    }
+    pieces
+}
+
+/// Smallest unit of a rendered line that must stay intact when splitting.
+#[derive(Clone, Copy)]
+enum Atom<'a> {
+    /// A single character or a backslash escape sequence.
+    Text(&'a str),
+    /// An entity delimiter such as `*` or `__`.
+    Marker(&'static str),
+    /// An inline link with its escaped text and URL.
+    Link { text: &'a str, url: &'a str },
+    /// Inline code including its backticks.
+    Code(&'a str),
+}
 
-    fn needs_escape(c: char) -> bool {
-        matches!(c, '-' | '>' | '#' | '+' | '=' | '{' | '}' | '.' | '!')
+impl Atom<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Atom::Text(text) | Atom::Code(text) => text.len(),
+            Atom::Marker(marker) => marker.len(),
+            Atom::Link { text, url } => text.len() + url.len() + 4,
+        }
     }
 
-    for line in text.lines() {
-        if line.trim() == "```" {
-            let extra = if current.is_empty() { 3 } else { 4 };
-            if current.len() + extra > limit && !current.is_empty() {
-                if in_code_block {
-                    current.push_str("\n```");
-                    posts.push(current.clone());
-                    current.clear();
-                    current.push_str("```");
-                } else {
-                    posts.push(current.clone());
-                    current.clear();
-                }
-            } else if !current.is_empty() {
-                current.push('\n');
+    fn push_to(&self, out: &mut String) {
+        match self {
+            Atom::Text(text) | Atom::Code(text) => out.push_str(text),
+            Atom::Marker(marker) => out.push_str(marker),
+            Atom::Link { text, url } => {
+                out.push('[');
+                out.push_str(text);
+                out.push_str("](");
+                out.push_str(url);
+                out.push(')');
             }
-            current.push_str("```");
-            in_code_block = !in_code_block;
+        }
+    }
+}
+
+/// Find the end of a bracketed group starting at `open`, skipping escapes.
+fn find_closing(line: &str, open: usize, close: char) -> Option<usize> {
+    let mut chars = line[open + 1..].char_indices();
+    while let Some((idx, c)) = chars.next() {
+        if c == '\\' {
+            chars.next();
+        } else if c == close {
+            return Some(open + 1 + idx);
+        }
+    }
+    None
+}
+
+/// Split `line` into atoms; `glossary` holds escaped terms kept as one atom.
+fn tokenize_line<'a>(line: &'a str, glossary: &Glossary) -> Vec<Atom<'a>> {
+    let mut atoms = Vec::new();
+    let mut idx = 0;
+    while let Some(c) = line[idx..].chars().next() {
+        let rest = &line[idx..];
+        if let Some(len) = glossary.term_at(line, idx) {
+            atoms.push(Atom::Text(&rest[..len]));
+            idx += len;
             continue;
         }
-        if line.len() > limit {
-            if !current.is_empty() {
-                posts.push(current.clone());
-                current.clear();
+        let (atom, consumed) = match c {
+            '\\' => {
+                let next_len = rest[1..].chars().next().map_or(0, char::len_utf8);
+                (Atom::Text(&rest[..1 + next_len]), 1 + next_len)
             }
+            '[' => match find_closing(line, idx, ']') {
+                Some(close) if line[close + 1..].starts_with('(') => {
+                    match find_closing(line, close + 1, ')') {
+                        Some(end) => (
+                            Atom::Link {
+                                text: &line[idx + 1..close],
+                                url: &line[close + 2..end],
+                            },
+                            end + 1 - idx,
+                        ),
+                        None => (Atom::Text("["), 1),
+                    }
+                }
+                _ => (Atom::Text("["), 1),
+            },
+            '*' if rest.starts_with("**") => (Atom::Marker("**"), 2),
+            '*' => (Atom::Marker("*"), 1),
+            '_' if rest.starts_with("__") => (Atom::Marker("__"), 2),
+            '_' => (Atom::Marker("_"), 1),
+            '~' => (Atom::Marker("~"), 1),
+            '|' if rest.starts_with("||") => (Atom::Marker("||"), 2),
+            '`' if rest.starts_with("```") => (Atom::Marker("```"), 3),
+            '`' => match find_closing(line, idx, '`') {
+                Some(end) => (Atom::Code(&line[idx..=end]), end + 1 - idx),
+                None => (Atom::Marker("`"), 1),
+            },
+            _ => (Atom::Text(&rest[..c.len_utf8()]), c.len_utf8()),
+        };
+        atoms.push(atom);
+        idx += consumed;
+    }
+    atoms
+}
+
+/// Apply an entity delimiter to the stack of open entities.
+fn toggle_entity(stack: &mut Vec<&'static str>, marker: &'static str) {
+    if stack.last() == Some(&marker) {
+        stack.pop();
+    } else {
+        stack.push(marker);
+    }
+}
+
+fn entities_len(stack: &[&'static str]) -> usize {
+    stack.iter().map(|marker| marker.len()).sum()
+}
+
+fn ends_sentence(atom: Option<&Atom<'_>>) -> bool {
+    matches!(
+        atom,
+        Some(Atom::Text("\\." | "\\!" | "?" | ":" | ";")) | Some(Atom::Link { .. })
+    )
+}
+
+/// Render `atoms` as one chunk, reopening `open` entities at the start and
+/// closing `close` entities at the end.
+fn render_atoms(atoms: &[Atom<'_>], open: &[&'static str], close: &[&'static str]) -> String {
+    let mut out = String::new();
+    for marker in open {
+        out.push_str(marker);
+    }
+    for (idx, atom) in atoms.iter().enumerate() {
+        let at_edge = idx == 0 || idx + 1 == atoms.len();
+        match atom {
+            Atom::Text(text) if at_edge && text.chars().next().is_some_and(needs_escape) => {
+                out.push('\\');
+                out.push_str(text);
+            }
+            Atom::Text("\\") if idx + 1 == atoms.len() => {}
+            _ => atom.push_to(&mut out),
+        }
+    }
+    for marker in close.iter().rev() {
+        out.push_str(marker);
+    }
+    out
+}
 
-            let mut chunk = String::new();
-            for c in line.chars() {
-                if chunk.len() + c.len_utf8() > limit {
-                    if chunk.ends_with('\\') {
-                        chunk.pop();
-                        posts.push(chunk.clone());
-                        chunk.clear();
-                        chunk.push('\\');
-                    } else {
-                        posts.push(chunk.clone());
-                        chunk.clear();
-                        if needs_escape(c) {
-                            chunk.push('\\');
+/// Split a link whose text alone exceeds the budget into several links that
+/// share the same URL.
+fn split_link(text: &str, url: &str, budget: usize, glossary: &Glossary) -> Vec<String> {
+    let text_budget = budget.saturating_sub(url.len() + 4).max(1);
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for atom in tokenize_line(text, glossary) {
+        let mut rendered = String::new();
+        atom.push_to(&mut rendered);
+        if !current.is_empty() && current.len() + rendered.len() > text_budget {
+            pieces.push(format!("[{}]({url})", current.trim()));
+            current.clear();
+        }
+        current.push_str(&rendered);
+    }
+    if !current.trim().is_empty() {
+        pieces.push(format!("[{}]({url})", current.trim()));
+    }
+    pieces
+}
+
+/// Split a single line that exceeds `limit` without breaking entities.
+fn split_long_line(line: &str, limit: usize, glossary: &Glossary) -> Vec<String> {
+    // Room for escaping a control character at either edge of a chunk.
+    const EDGE_RESERVE: usize = 2;
+    let budget = limit.saturating_sub(EDGE_RESERVE);
+    let atoms = tokenize_line(line, glossary);
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut start_stack: Vec<&'static str> = Vec::new();
+
+    while start < atoms.len() {
+        let mut stack = start_stack.clone();
+        let mut len = entities_len(&stack);
+        let mut sentence_break: Option<(usize, Vec<&'static str>)> = None;
+        let mut word_break: Option<(usize, Vec<&'static str>)> = None;
+        let mut end = start;
+
+        while end < atoms.len() {
+            let atom = &atoms[end];
+            let mut next_stack = stack.clone();
+            if let Atom::Marker(marker) = atom {
+                toggle_entity(&mut next_stack, marker);
+            }
+            if len + atom.len() + entities_len(&next_stack) > budget {
+                break;
+            }
+            len += atom.len();
+            stack = next_stack;
+            end += 1;
+            if matches!(atom, Atom::Text(" ")) && len * 2 >= budget {
+                if ends_sentence(end.checked_sub(2).and_then(|idx| atoms.get(idx))) {
+                    sentence_break = Some((end, stack.clone()));
+                }
+                word_break = Some((end, stack.clone()));
+            }
+        }
+
+        if end == start {
+            // A single atom does not fit: only links can be divided further.
+            let atom = atoms[start];
+            match atom {
+                Atom::Link { text, url } => {
+                    let overhead = entities_len(&start_stack) * 2;
+                    for link in split_link(text, url, budget.saturating_sub(overhead), glossary) {
+                        let mut piece = start_stack.concat();
+                        piece.push_str(&link);
+                        for marker in start_stack.iter().rev() {
+                            piece.push_str(marker);
                         }
+                        pieces.push(piece);
                     }
                 }
-                chunk.push(c);
+                _ => {
+                    let mut piece = String::new();
+                    atom.push_to(&mut piece);
+                    pieces.push(piece);
+                }
             }
-            if !chunk.is_empty() {
-                posts.push(chunk);
+            if let Atom::Marker(marker) = atom {
+                toggle_entity(&mut start_stack, marker);
             }
-            join_next = false;
+            start += 1;
             continue;
         }
 
-        let new_len = if current.is_empty() {
-            line.len()
-        } else if join_next {
-            current.len() + line.len()
+        let (split_at, end_stack) = if end == atoms.len() {
+            (end, stack)
         } else {
-            current.len() + 1 + line.len()
+            sentence_break.or(word_break).unwrap_or((end, stack))
         };
-
-        if new_len > limit && !current.is_empty() {
-            if in_code_block {
-                current.push_str("\n```");
-                posts.push(current.clone());
-                current.clear();
-                current.push_str("```");
-            } else if current.ends_with('\\') {
-                current.pop();
-                posts.push(current.clone());
-                current.clear();
-                current.push('\\');
-                join_next = true;
-            } else {
-                posts.push(current.clone());
-                current.clear();
-                join_next = false;
-            }
+        let mut head_end = split_at;
+        while head_end > start && matches!(atoms[head_end - 1], Atom::Text(" ")) {
+            head_end -= 1;
         }
-
-        if !current.is_empty() && !join_next {
-            current.push('\n');
-        }
-        if current.is_empty()
-            && let Some(first) = line.chars().next()
-            && needs_escape(first)
-            && !line.starts_with('\\')
-        {
-            current.push('\\');
-        }
-        current.push_str(line);
-        if join_next {
-            current.push('\n');
-            join_next = false;
+        pieces.push(render_atoms(
+            &atoms[start..head_end],
+            &start_stack,
+            &end_stack,
+        ));
+        start = split_at;
+        while start < atoms.len() && matches!(atoms[start], Atom::Text(" ")) {
+            start += 1;
         }
+        start_stack = end_stack;
     }
 
-    if !current.is_empty() {
-        posts.push(current);
-    }
-
-    posts
+    pieces.retain(|piece| !piece.is_empty());
+    pieces
 }
 
 fn preprocess_issue_input(input: String) -> String {
     input.replace("_Полный выпуск: ссылка_", "")
 }
 
-fn jobs_resources() -> [String; 3] {
-    [
-        format!(
-            "💼 [Rust Jobs chat]({})",
-            escape_markdown_url("https://t.me/rust_jobs")
-        ),
-        format!(
-            "📢 [Rust Jobs feed]({})",
-            escape_markdown_url("https://t.me/rust_jobs_feed")
-        ),
-        format!(
-            "📝 [Rust HH jobs]({})",
-            escape_markdown_url("https://t.me/rusthhjobs")
-        ),
-    ]
+fn translate_sections(sections: &mut [Section], translator: &dyn Translator, glossary: &Glossary) {
+    let failures: Vec<_> = sections
+        .iter_mut()
+        .flat_map(|section| translate_section(section, translator, glossary))
+        .collect();
+    if let Some(first) = failures.first() {
+        warn!("{} texts left untranslated: {first}", failures.len());
+    }
 }
 
-fn transform_section(section: &mut Section) {
-    if section.title.eq_ignore_ascii_case("Jobs") && !section.lines.is_empty() {
-        simplify_jobs_section(section);
-        section.lines.splice(1..1, jobs_resources());
+fn log_parse_report(report: &ParseReport) {
+    for reference in &report.broken_references {
+        warn!("Broken link reference [{reference}]");
     }
-    if section
-        .title
-        .eq_ignore_ascii_case("Call for Participation; projects and speakers")
-    {
-        simplify_cfp_section(section);
+    for html in &report.dropped_html {
+        warn!("Dropped raw HTML: {html}");
     }
-    simplify_quote_section(section);
 }
 
-fn prepare_sections(body: &str, web_url: Option<&str>) -> Vec<Section> {
-    let mut sections = parse_sections(body);
+/// Parse the FCP items of `body` with their links rewritten by `urls`.
+fn issue_fcp(body: &str, urls: &UrlOptions) -> Vec<FcpItem> {
+    let mut items = parse_fcp(body);
+    items.iter_mut().for_each(|item| item.rewrite_urls(urls));
+    items
+}
+
+/// Parse the teams without FCP items of `body` with their links rewritten by
+/// `urls`.
+fn issue_quiet_teams(body: &str, urls: &UrlOptions) -> Vec<QuietTeam> {
+    let mut teams = parse_quiet_teams(body);
+    teams.iter_mut().for_each(|team| team.rewrite_urls(urls));
+    teams
+}
+
+/// Build the standalone FCP post of `body` when `[fcp] standalone` is set and
+/// the section policy keeps the Final Comment Period listing.
+fn standalone_fcp(body: &str, options: &GenerationOptions, messages: &Messages) -> Option<Section> {
+    if !options.fcp.standalone {
+        return None;
+    }
+    let (sections, _) = parse_sections_report(body, &options.urls);
+    let is_fcp = |title: &str| title.eq_ignore_ascii_case(FCP_SECTION);
+    let kept = apply_section_policy(sections, &options.sections, messages)
+        .iter()
+        .any(|section| {
+            is_fcp(&section.title) || section.subheadings.iter().any(|sub| is_fcp(&sub.title))
+        });
+    if !kept {
+        return None;
+    }
+    standalone_fcp_section(
+        &issue_fcp(body, &options.urls),
+        &issue_quiet_teams(body, &options.urls),
+        messages,
+    )
+}
+
+/// Parse the upcoming events of `body` with their links rewritten by `urls`.
+fn issue_events(body: &str, urls: &UrlOptions) -> Vec<Event> {
+    let mut events = parse_events(body);
+    events.iter_mut().for_each(|event| event.rewrite_urls(urls));
+    events
+}
+
+fn prepare_sections(
+    body: &str,
+    web_url: Option<&str>,
+    options: &GenerationOptions,
+    transforms: &TransformRegistry,
+    translator: Option<&dyn Translator>,
+) -> Vec<Section> {
+    let messages = options.locale.messages();
+    for name in &options.transforms.disable {
+        if !transforms
+            .names()
+            .any(|known| known.eq_ignore_ascii_case(name.trim()))
+        {
+            warn!("Unknown section transform {name:?} in [transforms] disable");
+        }
+    }
+    let (sections, report) = parse_sections_report(body, &options.urls);
+    log_parse_report(&report);
+    let mut sections = apply_section_policy(sections, &options.sections, messages);
+    let fcp_items = issue_fcp(body, &options.urls);
+    if !fcp_items.is_empty() {
+        let replacement = (!options.fcp.standalone).then(|| {
+            let mut lines = render_fcp(&fcp_items);
+            lines.extend(render_quiet_teams(
+                &issue_quiet_teams(body, &options.urls),
+                messages,
+            ));
+            lines
+        });
+        for section in &mut sections {
+            if replace_fcp_listing(section, &fcp_items, replacement.clone()) {
+                break;
+            }
+        }
+        sections.retain(|section| {
+            !section.title.eq_ignore_ascii_case(FCP_SECTION)
+                || section.lines.iter().any(|line| !line.trim().is_empty())
+        });
+    }
+    if options.quote.detached() {
+        sections.retain(|section| {
+            !section.title.eq_ignore_ascii_case(QUOTE_SECTION)
+                || section.lines.iter().any(|line| !line.trim().is_empty())
+        });
+    }
+    let mut pulls = parse_pull_requests(body);
+    pulls.rewrite_urls(&options.urls);
+    let perf = parse_perf_triage(body).map(|mut triage| {
+        triage.rewrite_urls(&options.urls);
+        triage.render(messages)
+    });
     for section in &mut sections {
-        transform_section(section);
+        apply_pull_digest(section, &pulls, &options.pulls, web_url, messages);
+        if let Some(card) = &perf {
+            replace_perf_triage(section, card.clone());
+        }
+        if let Some(index) = &options.crates.index {
+            enrich_crate_of_week(section, index);
+        }
+        transforms.apply(section, messages, &options.transforms.disable);
+        apply_media_tags(section, &options.tags);
+    }
+    if let Some(digest) = options.events.as_ref().filter(|digest| !digest.standalone) {
+        let position = sections
+            .iter()
+            .position(|section| section.title.eq_ignore_ascii_case(EVENTS_SECTION));
+        match (
+            digest.render(&issue_events(body, &options.urls), messages),
+            position,
+        ) {
+            (Some(rendered), Some(index)) => sections[index] = rendered,
+            (Some(rendered), None) if options.sections.allows(EVENTS_SECTION) => {
+                sections.push(rendered)
+            }
+            (None, Some(index)) => {
+                sections.remove(index);
+            }
+            _ => {}
+        }
+    }
+    apply_rules(&mut sections, &options.rules);
+    if let Some(translator) = translator {
+        translate_sections(&mut sections, translator, &options.glossary);
+    }
+    for section in &mut sections {
+        apply_theme(section, &options.theme, messages);
     }
-    sections.retain(|section| !section.title.eq_ignore_ascii_case("Upcoming Events"));
 
     if let Some(link) = web_url {
         let mut link_section = Section::default();
         link_section.lines.push(String::new());
         link_section.lines.push(format!(
-            "🌐 [View web version]({}) 🌐",
+            "🌐 [{}]({}) 🌐",
+            escape(messages.view_web_version),
             escape_markdown_url(link)
         ));
         sections.push(link_section);
@@ -530,7 +950,12 @@ fn prepare_sections(body: &str, web_url: Option<&str>) -> Vec<Section> {
     sections
 }
 
-fn render_section_text(section: &Section, index: usize, header: &str) -> String {
+fn render_section_text(
+    section: &Section,
+    index: usize,
+    header: &str,
+    options: &GenerationOptions,
+) -> String {
     let mut text = String::new();
     if index == 0 {
         text.push_str(header);
@@ -539,7 +964,13 @@ fn render_section_text(section: &Section, index: usize, header: &str) -> String
         if index > 0 {
             text.push('\n');
         }
-        text.push_str(&format!("{}\n", format_heading(&section.title)));
+        let label = options.locale.messages().title(&section.title);
+        text.push_str(&format!(
+            "{}\n",
+            options
+                .theme
+                .heading_as(&section.title, label, &options.glossary)
+        ));
     }
     for line in &section.lines {
         text.push_str(line);
@@ -548,12 +979,16 @@ fn render_section_text(section: &Section, index: usize, header: &str) -> String
     text
 }
 
-fn bundle_sections_into_posts(sections: &[Section], header: &str) -> Vec<String> {
+fn bundle_sections_into_posts(
+    sections: &[Section],
+    header: &str,
+    options: &GenerationOptions,
+) -> Vec<String> {
     let mut posts = Vec::new();
     let mut current_post = String::new();
 
     for (index, section) in sections.iter().enumerate() {
-        let section_text = render_section_text(section, index, header);
+        let section_text = render_section_text(section, index, header, options);
         if !current_post.is_empty() && current_post.len() + section_text.len() > TELEGRAM_LIMIT {
             posts.push(current_post);
             current_post = section_text;
@@ -562,70 +997,530 @@ fn bundle_sections_into_posts(sections: &[Section], header: &str) -> Vec<String>
         }
     }
 
-    if !current_post.is_empty() {
-        posts.push(current_post);
+    if !current_post.is_empty() {
+        posts.push(current_post);
+    }
+
+    posts
+}
+
+/// Break rendered sections into blocks that balanced packing may move
+/// between posts.
+///
+/// A block starts at every section, blank line and subheading; blocks that
+/// exceed the Telegram limit on their own are pre-split with
+/// [`split_posts_with_glossary`], leaving room for the restored line break.
+fn section_blocks(sections: &[Section], header: &str, options: &GenerationOptions) -> Vec<String> {
+    fn push_block(blocks: &mut Vec<String>, block: String, glossary: &Glossary) {
+        if block.len() > TELEGRAM_LIMIT {
+            blocks.extend(
+                split_posts_with_glossary(&block, TELEGRAM_LIMIT - 1, glossary)
+                    .into_iter()
+                    .map(|piece| piece + "\n"),
+            );
+        } else if !block.is_empty() {
+            blocks.push(block);
+        }
+    }
+
+    let mut blocks = Vec::new();
+    for (index, section) in sections.iter().enumerate() {
+        let text = render_section_text(section, index, header, options);
+        let mut block = String::new();
+        for line in classify_lines(&text) {
+            let starts_block = line.priority >= BreakPriority::Subheading
+                || (line.priority > BreakPriority::Anywhere && line.text.trim().is_empty());
+            if starts_block && !block.trim().is_empty() {
+                push_block(&mut blocks, std::mem::take(&mut block), &options.glossary);
+            }
+            block.push_str(line.text);
+            block.push('\n');
+        }
+        push_block(&mut blocks, block, &options.glossary);
+    }
+    blocks
+}
+
+fn split_posts_to_telegram_limit(posts: Vec<String>, glossary: &Glossary) -> Vec<String> {
+    let mut final_posts = Vec::new();
+    for post in posts {
+        if post.len() > TELEGRAM_LIMIT {
+            final_posts.extend(split_posts_with_glossary(&post, TELEGRAM_LIMIT, glossary));
+        } else {
+            final_posts.push(post);
+        }
+    }
+    final_posts
+}
+
+fn finalize_posts(posts: Vec<String>, messages: &Messages) -> Result<Vec<String>, ValidationError> {
+    let total = posts.len();
+    let mut result = Vec::new();
+
+    for (index, mut post) in posts.into_iter().enumerate() {
+        if !post.ends_with('\n') {
+            post.push('\n');
+        }
+        let formatted = if index == 0 {
+            post.trim_start_matches('\n').to_string()
+        } else {
+            format!(
+                "*{} {}/{}*\n\n{}",
+                escape(messages.part),
+                index + 1,
+                total,
+                post.trim_start_matches('\n')
+            )
+        };
+        validate_telegram_markdown(&formatted)
+            .map_err(|e| ValidationError(format!("Generated post {} invalid: {e}", index + 1)))?;
+        result.push(formatted);
+    }
+
+    Ok(result)
+}
+
+/// Convert a TWIR Markdown file into a series of Telegram posts.
+///
+/// The input may include metadata headers which will be used to build the
+/// leading post and the final link section.
+///
+/// # Parameters
+/// - `input`: Raw Markdown content read from a TWIR issue.
+///
+/// # Returns
+/// A vector of validated Telegram Markdown posts or a `ValidationError` if any
+/// post fails validation.
+pub fn generate_posts(input: String) -> Result<Vec<String>, ValidationError> {
+    generate_posts_with_options(input, &GenerationOptions::default())
+}
+
+/// Report broken references and dropped raw HTML for an issue.
+///
+/// # Parameters
+/// - `input`: Full issue Markdown including the metadata header.
+/// - `options`: Options whose `[urls]` rewrites are applied while parsing.
+///
+/// # Returns
+/// The [`ParseReport`] of the issue body.
+pub fn parse_report(input: &str, options: &GenerationOptions) -> ParseReport {
+    let input = preprocess_issue_input(input.to_string());
+    parse_sections_report(&strip_header(&input), &options.urls).1
+}
+
+/// Convert a TWIR Markdown file into Telegram posts using `options`.
+///
+/// Regardless of the packing strategy the web version link never ends up
+/// alone in the final post.
+///
+/// # Parameters
+/// - `input`: Raw Markdown content read from a TWIR issue.
+/// - `options`: Generation settings such as the packing strategy and the
+///   section policy.
+///
+/// # Returns
+/// A vector of validated Telegram Markdown posts or a `ValidationError` if any
+/// post fails validation or the `[translate]` settings are unusable.
+pub fn generate_posts_with_options(
+    input: String,
+    options: &GenerationOptions,
+) -> Result<Vec<String>, ValidationError> {
+    generate_issue(input, options).map(|issue| issue.posts)
+}
+
+/// Convert a TWIR Markdown file into Telegram posts using `options`, keeping
+/// the titles of the sections they hold.
+///
+/// Pass the result to [`generate_toc`] and [`generate_cover`] so they reuse
+/// the sections of this generation pass.
+///
+/// # Parameters
+/// - `input`: Raw Markdown content read from a TWIR issue.
+/// - `options`: Generation settings of the delivery target.
+///
+/// # Returns
+/// The posts and section titles, or a `ValidationError` as
+/// [`generate_posts_with_options`] does.
+pub fn generate_issue(
+    input: String,
+    options: &GenerationOptions,
+) -> Result<GeneratedIssue, ValidationError> {
+    let translator = options
+        .translate
+        .as_ref()
+        .map(TranslateOptions::translator)
+        .transpose()
+        .map_err(|e| ValidationError(e.to_string()))?;
+    let issue = generate_issue_with_transforms(
+        input,
+        options,
+        &TransformRegistry::with_builtins_for(options),
+        translator.as_deref(),
+    )?;
+    if let Some(translator) = &translator
+        && let Err(err) = translator.flush()
+    {
+        warn!("Failed to save translations: {err}");
+    }
+    Ok(issue)
+}
+
+/// Convert a TWIR Markdown file into Telegram posts, translating the content
+/// of every section with `translator`.
+///
+/// The `[translate]` settings of `options` are ignored; link URLs, code and
+/// generated labels are not sent to the translator, and text it cannot
+/// translate is published as written.
+///
+/// # Parameters
+/// - `input`: Raw Markdown content read from a TWIR issue.
+/// - `options`: Generation settings of the delivery target.
+/// - `translator`: Translation stage run between parsing and rendering.
+///
+/// # Returns
+/// A vector of validated Telegram Markdown posts or a `ValidationError` if any
+/// post fails validation.
+pub fn generate_posts_with_translator(
+    input: String,
+    options: &GenerationOptions,
+    translator: Option<&dyn Translator>,
+) -> Result<Vec<String>, ValidationError> {
+    generate_posts_with_transforms(
+        input,
+        options,
+        &TransformRegistry::with_builtins_for(options),
+        translator,
+    )
+}
+
+/// Convert a TWIR Markdown file into Telegram posts, rewriting sections with
+/// the transforms of `transforms` and translating them with `translator`.
+///
+/// Transforms named in `[transforms] disable` of `options` are skipped.
+///
+/// # Parameters
+/// - `input`: Raw Markdown content read from a TWIR issue.
+/// - `options`: Generation settings of the delivery target.
+/// - `transforms`: Section transforms, usually
+///   [`TransformRegistry::with_builtins_for`] plus custom ones.
+/// - `translator`: Optional translation stage, see
+///   [`generate_posts_with_translator`].
+///
+/// # Returns
+/// A vector of validated Telegram Markdown posts or a `ValidationError` if any
+/// post fails validation.
+pub fn generate_posts_with_transforms(
+    input: String,
+    options: &GenerationOptions,
+    transforms: &TransformRegistry,
+    translator: Option<&dyn Translator>,
+) -> Result<Vec<String>, ValidationError> {
+    generate_issue_with_transforms(input, options, transforms, translator).map(|issue| issue.posts)
+}
+
+fn generate_issue_with_transforms(
+    input: String,
+    options: &GenerationOptions,
+    transforms: &TransformRegistry,
+    translator: Option<&dyn Translator>,
+) -> Result<GeneratedIssue, ValidationError> {
+    let metadata = IssueMetadata::from_input(&input);
+    let input = preprocess_issue_input(input);
+    let body = strip_header(&input);
+    let sections = prepare_sections(
+        &body,
+        metadata.url.as_deref(),
+        options,
+        transforms,
+        translator,
+    );
+    let header = metadata.header();
+    let has_footer = metadata.url.is_some();
+    let messages = options.locale.messages();
+    let mut standalone = Vec::new();
+    let greedy = || {
+        let bundled = bundle_sections_into_posts(&sections, &header, options);
+        let split = split_posts_to_telegram_limit(bundled, &options.glossary);
+        match sections.last() {
+            Some(footer) if has_footer => {
+                let footer = render_section_text(footer, sections.len() - 1, &header, options);
+                keep_footer_attached(split, &footer, TELEGRAM_LIMIT)
+            }
+            _ => split,
+        }
+    };
+    let mut posts = match options.packing {
+        PackingStrategy::Greedy => greedy(),
+        PackingStrategy::Balanced => {
+            let blocks = section_blocks(&sections, &header, options);
+            match pack_balanced(&blocks, TELEGRAM_LIMIT, has_footer) {
+                Some(packed) => split_posts_to_telegram_limit(packed, &options.glossary),
+                None => {
+                    warn!("Balanced packing found no layout; falling back to greedy packing");
+                    greedy()
+                }
+            }
+        }
+    };
+    if let Some(digest) = options.events.as_ref().filter(|digest| digest.standalone)
+        && let Some(mut section) = digest.render(&issue_events(&body, &options.urls), messages)
+    {
+        if let Some(translator) = translator {
+            translate_sections(
+                std::slice::from_mut(&mut section),
+                translator,
+                &options.glossary,
+            );
+        }
+        apply_theme(&mut section, &options.theme, messages);
+        let text = render_section_text(&section, 0, "", options);
+        posts.extend(split_posts_to_telegram_limit(vec![text], &options.glossary));
+        standalone.push(section.title);
+    }
+    if let Some(mut section) = standalone_fcp(&body, options, messages) {
+        if let Some(translator) = translator {
+            translate_sections(
+                std::slice::from_mut(&mut section),
+                translator,
+                &options.glossary,
+            );
+        }
+        apply_theme(&mut section, &options.theme, messages);
+        let text = render_section_text(&section, 0, "", options);
+        posts.extend(split_posts_to_telegram_limit(vec![text], &options.glossary));
+        standalone.push(section.title);
+    }
+    if options.quote.standalone
+        && options.quote.card.is_none()
+        && let Some(quote) = parse_quote(&body)
+    {
+        let mut section = quote.to_section();
+        if let Some(translator) = translator {
+            translate_sections(
+                std::slice::from_mut(&mut section),
+                translator,
+                &options.glossary,
+            );
+        }
+        let text = render_section_text(&section, 0, "", options);
+        posts.extend(split_posts_to_telegram_limit(vec![text], &options.glossary));
+        standalone.push(section.title);
+    }
+    Ok(GeneratedIssue {
+        posts: finalize_posts(posts, messages)?,
+        sections: sections.into_iter().map(|section| section.title).collect(),
+        standalone,
+    })
+}
+
+/// Convert the Upcoming Events section of a TWIR issue into an iCalendar file.
+///
+/// When `options` configure an event digest only events matching its regions
+/// or cities are exported.
+///
+/// # Parameters
+/// - `input`: Raw Markdown content read from a TWIR issue.
+/// - `options`: Generation settings of the delivery target.
+///
+/// # Returns
+/// The calendar text, or `None` when the issue lists no matching events.
+pub fn generate_calendar(input: &str, options: &GenerationOptions) -> Option<String> {
+    let metadata = IssueMetadata::from_input(input);
+    let events: Vec<Event> = issue_events(&strip_header(input), &options.urls)
+        .into_iter()
+        .filter(|event| options.events.as_ref().is_none_or(|d| d.matches(event)))
+        .collect();
+    if events.is_empty() {
+        return None;
     }
-
-    posts
+    let name = match metadata.number.as_deref() {
+        Some(number) => format!("This Week in Rust #{number} events"),
+        None => "This Week in Rust events".to_string(),
+    };
+    Some(events_to_ical(&events, &name, metadata.date.as_deref()))
 }
 
-fn split_posts_to_telegram_limit(posts: Vec<String>) -> Vec<String> {
-    let mut final_posts = Vec::new();
-    for post in posts {
-        if post.len() > TELEGRAM_LIMIT {
-            final_posts.extend(split_posts(&post, TELEGRAM_LIMIT));
-        } else {
-            final_posts.push(post);
-        }
-    }
-    final_posts
+/// Locate the section headings of a TWIR issue in its generated posts.
+///
+/// Standalone Upcoming Events, Final Comment Period and quote posts are
+/// listed after the issue sections.
+///
+/// # Parameters
+/// - `issue`: Posts and section titles from [`generate_issue`], before any
+///   plain text conversion.
+/// - `options`: Generation settings `issue` was generated with.
+///
+/// # Returns
+/// The localized section titles with the index of the post each starts in.
+pub fn generate_toc(issue: &GeneratedIssue, options: &GenerationOptions) -> Vec<TocEntry> {
+    let messages = options.locale.messages();
+    let titles = issue.sections.iter().chain(&issue.standalone);
+    let headings: Vec<(String, String)> = titles
+        .filter(|title| !title.is_empty())
+        .map(|title| {
+            let label = messages.title(title);
+            let heading = options.theme.heading_as(title, label, &options.glossary);
+            let first = heading.lines().next().unwrap_or_default().to_string();
+            (first, label.to_string())
+        })
+        .collect();
+    locate_headings(&issue.posts, &headings)
 }
 
-fn finalize_posts(posts: Vec<String>) -> Result<Vec<String>, ValidationError> {
-    let total = posts.len();
-    let mut result = Vec::new();
+/// Build the cover photo sent before the posts of a TWIR issue.
+///
+/// The caption names the issue, its date and the published sections, cut to
+/// [`TELEGRAM_CAPTION_LIMIT`].
+///
+/// # Parameters
+/// - `input`: Raw Markdown content read from a TWIR issue.
+/// - `issue`: Posts and section titles generated from `input` by
+///   [`generate_issue`].
+/// - `options`: Generation settings of the delivery target.
+///
+/// # Returns
+/// The configured `[cover] image`, a cover drawn with `[cover.card]`, or
+/// `None` when neither is set.
+///
+/// # Errors
+/// Returns an error if the image cannot be read or the cover cannot be
+/// rendered, see [`render_cover`].
+pub fn generate_cover(
+    input: &str,
+    issue: &GeneratedIssue,
+    options: &GenerationOptions,
+) -> std::io::Result<Option<PhotoPost>> {
+    if options.cover.image.is_none() && options.cover.card.is_none() {
+        return Ok(None);
+    }
+    let metadata = IssueMetadata::from_input(input);
+    let messages = options.locale.messages();
+    let subtitle = [
+        metadata.number.as_ref().map(|number| format!("#{number}")),
+        metadata.date.clone(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" — ");
+    let cover = Cover {
+        title: metadata
+            .title
+            .clone()
+            .unwrap_or_else(|| "This Week in Rust".to_string()),
+        subtitle,
+        sections: issue
+            .sections
+            .iter()
+            .filter(|title| !title.is_empty())
+            .map(|title| messages.title(title).to_string())
+            .collect(),
+    };
+    let caption = cover.caption(TELEGRAM_CAPTION_LIMIT);
+    let photo = match (&options.cover.image, &options.cover.card) {
+        (Some(path), _) => PhotoPost {
+            image: fs::read(path)?,
+            file_name: path.file_name().map_or(COVER_FILE.into(), |name| {
+                name.to_string_lossy().into_owned()
+            }),
+            caption,
+        },
+        (None, Some(card)) => PhotoPost {
+            image: render_cover(&cover, card)?,
+            file_name: COVER_FILE.to_string(),
+            caption,
+        },
+        (None, None) => return Ok(None),
+    };
+    Ok(Some(photo))
+}
 
-    for (index, mut post) in posts.into_iter().enumerate() {
-        if !post.ends_with('\n') {
-            post.push('\n');
-        }
-        let formatted = if index == 0 {
-            post.trim_start_matches('\n').to_string()
-        } else {
-            format!(
-                "*Part {}/{}*\n\n{}",
-                index + 1,
-                total,
-                post.trim_start_matches('\n')
-            )
-        };
-        validate_telegram_markdown(&formatted)
-            .map_err(|e| ValidationError(format!("Generated post {} invalid: {e}", index + 1)))?;
-        result.push(formatted);
-    }
+/// Render the Quote of the Week of a TWIR issue as an image card.
+///
+/// The caption holds the section heading and the attribution; the quote is
+/// drawn untranslated.
+///
+/// # Parameters
+/// - `input`: Raw Markdown content read from a TWIR issue.
+/// - `options`: Generation settings of the delivery target.
+///
+/// # Returns
+/// `None` when `[quote.card]` is not configured or the issue has no quote.
+///
+/// # Errors
+/// Returns an error if the card cannot be rendered, see
+/// [`render_quote_card`].
+pub fn generate_quote_card(
+    input: &str,
+    options: &GenerationOptions,
+) -> std::io::Result<Option<PhotoPost>> {
+    let Some(card) = &options.quote.card else {
+        return Ok(None);
+    };
+    let body = strip_header(&preprocess_issue_input(input.to_string()));
+    let Some(quote) = parse_quote(&body) else {
+        return Ok(None);
+    };
+    let title = options.locale.messages().title(QUOTE_SECTION);
+    let image = render_quote_card(&quote, title, card)?;
+    let caption = Section {
+        title: QUOTE_SECTION.to_string(),
+        lines: quote.attribution_line().into_iter().collect(),
+        ..Section::default()
+    };
+    let caption = render_section_text(&caption, 0, "", options);
+    Ok(Some(PhotoPost {
+        image,
+        file_name: QUOTE_CARD_FILE.to_string(),
+        caption: caption.trim_end().to_string(),
+    }))
+}
 
-    Ok(result)
+/// Convert a TWIR Markdown file into a structured [`IssueDocument`].
+///
+/// Every section is exported in issue order; links are rewritten by the
+/// `[urls]` rules of `options` like in the posts, while the section policy
+/// and the Telegram-specific transforms are not applied.
+///
+/// # Parameters
+/// - `input`: Raw Markdown content read from a TWIR issue.
+/// - `options`: Generation settings of the delivery target.
+///
+/// # Returns
+/// The issue metadata together with its sections, subsections and items.
+pub fn generate_document(input: &str, options: &GenerationOptions) -> IssueDocument {
+    let metadata = IssueMetadata::from_input(input);
+    let body = strip_header(&preprocess_issue_input(input.to_string()));
+    let info = IssueInfo {
+        number: metadata.number,
+        date: metadata.date,
+        title: metadata.title,
+        url: metadata.url,
+    };
+    build_document(info, &parse_sections_with(&body, &options.urls))
 }
 
-/// Convert a TWIR Markdown file into a series of Telegram posts.
+/// Show how the rules of `options` change the sections of a TWIR issue.
 ///
-/// The input may include metadata headers which will be used to build the
-/// leading post and the final link section.
+/// The sections are prepared twice, without and with `options.rules`; the
+/// web version footer and translation are left out.
 ///
 /// # Parameters
 /// - `input`: Raw Markdown content read from a TWIR issue.
+/// - `options`: Generation settings holding the rules to check.
 ///
 /// # Returns
-/// A vector of validated Telegram Markdown posts or a `ValidationError` if any
-/// post fails validation.
-pub fn generate_posts(input: String) -> Result<Vec<String>, ValidationError> {
-    let metadata = IssueMetadata::from_input(&input);
-    let input = preprocess_issue_input(input);
-    let body = strip_header(&input);
-    let sections = prepare_sections(&body, metadata.url.as_deref());
-    let bundled = bundle_sections_into_posts(&sections, &metadata.header());
-    let split = split_posts_to_telegram_limit(bundled);
-    finalize_posts(split)
+/// The rendered lines of every section the rules changed, in issue order.
+pub fn preview_rules(input: &str, options: &GenerationOptions) -> Vec<RuleChange> {
+    let body = strip_header(&preprocess_issue_input(input.to_string()));
+    let transforms = TransformRegistry::with_builtins_for(options);
+    let without = GenerationOptions {
+        rules: Vec::new(),
+        ..options.clone()
+    };
+    let before = prepare_sections(&body, None, &without, &transforms, None);
+    let after = prepare_sections(&body, None, options, &transforms, None);
+    rule_changes(&before, &after)
 }
 
 /// Write generated posts to `output_N.md` files in `dir`.
@@ -696,6 +1591,22 @@ fn sanitize_url(url: &str, token: &str) -> String {
     url.replace(token, "<token>")
 }
 
+/// Outcome of delivering posts with [`send_to_telegram`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryReport {
+    /// Number of posts acknowledged by Telegram.
+    pub confirmed: usize,
+    /// Message identifiers returned by Telegram for each acknowledged post.
+    pub message_ids: Vec<i64>,
+}
+
+impl DeliveryReport {
+    /// Returns `true` when every expected post was acknowledged by Telegram.
+    pub fn all_confirmed(&self, expected: usize) -> bool {
+        self.confirmed == expected
+    }
+}
+
 /// Send prepared posts to a Telegram chat via the HTTP API.
 ///
 /// # Parameters
@@ -711,28 +1622,48 @@ fn sanitize_url(url: &str, token: &str) -> String {
 /// # Errors
 /// Returns an error if the HTTP request fails or Telegram responds with an
 /// error code.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct DeliveryReport {
-    /// Number of posts acknowledged by Telegram.
-    pub confirmed: usize,
-    /// Message identifiers returned by Telegram for each acknowledged post.
-    pub message_ids: Vec<i64>,
-}
-
-impl DeliveryReport {
-    /// Returns `true` when every expected post was acknowledged by Telegram.
-    pub fn all_confirmed(&self, expected: usize) -> bool {
-        self.confirmed == expected
-    }
+pub fn send_to_telegram(
+    posts: &[String],
+    base_url: &str,
+    token: &str,
+    chat_id: &str,
+    use_markdown: bool,
+    pin_first: bool,
+) -> Result<DeliveryReport, Box<dyn std::error::Error + Send + Sync>> {
+    send_to_telegram_threaded(
+        posts,
+        base_url,
+        token,
+        chat_id,
+        use_markdown,
+        pin_first,
+        &ThreadingOptions::default(),
+    )
 }
 
-pub fn send_to_telegram(
+/// Send prepared posts like [`send_to_telegram`], grouping them into a
+/// thread.
+///
+/// Parts 2..N reply to the first or previous part through
+/// `reply_parameters`, and every part goes to the forum topic of
+/// `message_thread_id` when one is set.
+///
+/// # Parameters
+/// - `posts`, `base_url`, `token`, `chat_id`, `use_markdown`, `pin_first`:
+///   See [`send_to_telegram`].
+/// - `threading`: Reply mode and forum topic of the delivery target.
+///
+/// # Errors
+/// Returns an error if the HTTP request fails or Telegram responds with an
+/// error code.
+pub fn send_to_telegram_threaded(
     posts: &[String],
     base_url: &str,
     token: &str,
     chat_id: &str,
     use_markdown: bool,
     pin_first: bool,
+    threading: &ThreadingOptions,
 ) -> Result<DeliveryReport, Box<dyn std::error::Error + Send + Sync>> {
     if use_markdown {
         for (i, post) in posts.iter().enumerate() {
@@ -761,11 +1692,19 @@ pub fn send_to_telegram(
         } else {
             None
         };
+        let thread_id = threading.message_thread_id.map(|id| id.to_string());
+        let reply = threading.reply_parameters(&message_ids);
         let mut form = vec![("chat_id", chat_id.as_ref()), ("text", post.as_str())];
         if let Some(mode) = parse_mode {
             form.push(("parse_mode", mode));
         }
         form.push(("disable_web_page_preview", "true"));
+        if let Some(thread_id) = &thread_id {
+            form.push(("message_thread_id", thread_id.as_str()));
+        }
+        if let Some(reply) = &reply {
+            form.push(("reply_parameters", reply.as_str()));
+        }
 
         let resp = client.post(&url).form(&form).send()?;
         let status = resp.status();
@@ -825,6 +1764,169 @@ pub fn send_to_telegram(
     })
 }
 
+/// Replace the text of a delivered message with `editMessageText`.
+///
+/// # Parameters
+/// - `text`: New message text.
+/// - `base_url`: Base Telegram API endpoint.
+/// - `token`: Bot token used for authentication.
+/// - `chat_id`: Identifier of the chat holding the message.
+/// - `message_id`: Identifier of the message to edit.
+/// - `use_markdown`: Whether `text` is Telegram Markdown.
+///
+/// # Errors
+/// Returns an error if `text` is invalid, the HTTP request fails or Telegram
+/// responds with an error code.
+pub fn edit_message_text(
+    text: &str,
+    base_url: &str,
+    token: &str,
+    chat_id: &str,
+    message_id: i64,
+    use_markdown: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if use_markdown {
+        validate_telegram_markdown(text)
+            .map_err(|e| ValidationError(format!("Edit of message {message_id} invalid: {e}")))?;
+    }
+    let client = Client::new();
+    let chat_id = normalize_chat_id(chat_id);
+    let url = format!(
+        "{}/bot{}/editMessageText",
+        base_url.trim_end_matches('/'),
+        token
+    );
+    debug!(
+        "Editing message {message_id} via {}",
+        sanitize_url(&url, token)
+    );
+    let message_id = message_id.to_string();
+    let mut form = vec![
+        ("chat_id", chat_id.as_ref()),
+        ("message_id", message_id.as_str()),
+        ("text", text),
+    ];
+    if use_markdown {
+        form.push(("parse_mode", "MarkdownV2"));
+    }
+    form.push(("disable_web_page_preview", "true"));
+    let resp = client.post(&url).form(&form).send()?;
+    let status = resp.status();
+    let body = resp.text()?;
+    debug!("Telegram edit response {status}: {body}");
+    let data: TelegramResponse = serde_json::from_str(&body)
+        .map_err(|e| format!("Failed to parse Telegram edit response: {e}: {body}"))?;
+    if !data.ok {
+        return Err(format!(
+            "Telegram API error when editing {message_id} {}: {}",
+            data.error_code.unwrap_or_default(),
+            data.description.unwrap_or_default()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Send an image post to a Telegram chat with `sendPhoto`.
+///
+/// # Parameters
+/// - `photo`: Image and caption to deliver.
+/// - `base_url`: Base Telegram API endpoint.
+/// - `token`: Bot token used for authentication.
+/// - `chat_id`: Identifier of the destination chat or channel.
+/// - `use_markdown`: Whether the caption is Telegram Markdown; otherwise it is
+///   sent as plain text.
+/// - `pin`: Pin the photo message like the first post of
+///   [`send_to_telegram`].
+/// - `message_thread_id`: Forum topic receiving the photo, see
+///   [`ThreadingOptions`].
+///
+/// # Returns
+/// The message identifier returned by Telegram.
+///
+/// # Errors
+/// Returns an error if the caption is invalid or longer than
+/// [`TELEGRAM_CAPTION_LIMIT`], the HTTP request fails or Telegram responds
+/// with an error code.
+pub fn send_photo_to_telegram(
+    photo: &PhotoPost,
+    base_url: &str,
+    token: &str,
+    chat_id: &str,
+    use_markdown: bool,
+    pin: bool,
+    message_thread_id: Option<i64>,
+) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+    let caption = if use_markdown {
+        validate_telegram_markdown(&photo.caption)
+            .map_err(|e| ValidationError(format!("Caption of {} invalid: {e}", photo.file_name)))?;
+        photo.caption.clone()
+    } else {
+        markdown_to_plain(&photo.caption)
+    };
+    if markdown_to_plain(&caption).chars().count() > TELEGRAM_CAPTION_LIMIT {
+        return Err(ValidationError(format!(
+            "Caption of {} exceeds {TELEGRAM_CAPTION_LIMIT} characters",
+            photo.file_name
+        ))
+        .into());
+    }
+    let client = Client::new();
+    let chat_id = normalize_chat_id(chat_id);
+    let url = format!("{}/bot{}/sendPhoto", base_url.trim_end_matches('/'), token);
+    debug!(
+        "Sending {} via {}",
+        photo.file_name,
+        sanitize_url(&url, token)
+    );
+    let mut part = multipart::Part::bytes(photo.image.clone()).file_name(photo.file_name.clone());
+    let extension = Path::new(&photo.file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    let mime = match extension.as_deref() {
+        Some("png") => Some("image/png"),
+        Some("jpg" | "jpeg") => Some("image/jpeg"),
+        Some("webp") => Some("image/webp"),
+        _ => None,
+    };
+    if let Some(mime) = mime {
+        part = part.mime_str(mime)?;
+    }
+    let mut form = multipart::Form::new()
+        .text("chat_id", chat_id.clone().into_owned())
+        .text("caption", caption)
+        .part("photo", part);
+    if use_markdown {
+        form = form.text("parse_mode", "MarkdownV2");
+    }
+    if let Some(thread_id) = message_thread_id {
+        form = form.text("message_thread_id", thread_id.to_string());
+    }
+    let resp = client.post(&url).multipart(form).send()?;
+    let status = resp.status();
+    let body = resp.text()?;
+    debug!("Telegram photo response {status}: {body}");
+    let raw: serde_json::Value = serde_json::from_str(&body)
+        .map_err(|e| format!("Failed to parse Telegram response: {e}: {body}"))?;
+    if !raw["ok"].as_bool().unwrap_or(false) {
+        return Err(format!(
+            "Telegram API error sending {} {}: {}",
+            photo.file_name,
+            raw["error_code"].as_i64().unwrap_or_default(),
+            raw["description"].as_str().unwrap_or("")
+        )
+        .into());
+    }
+    let id = raw["result"]["message_id"]
+        .as_i64()
+        .ok_or("Telegram response missing message_id")?;
+    if pin {
+        pin_first_message(&client, base_url, token, chat_id.as_ref(), id)?;
+    }
+    Ok(id)
+}
+
 fn pin_first_message(
     client: &Client,
     base_url: &str,
@@ -1001,6 +2103,129 @@ mod tests {
         assert_eq!(parts, vec!["aaa\nbbb", "ccc"]);
     }
 
+    #[test]
+    fn split_posts_prefers_section_boundaries() {
+        let text = "aaaaaaaa\nbbbb\n\n**Head**\ncccc\ndddd";
+        let parts = split_posts(text, 24);
+        assert_eq!(parts, vec!["aaaaaaaa\nbbbb", "**Head**\ncccc\ndddd"]);
+    }
+
+    #[test]
+    fn split_posts_keeps_stacked_headings_with_content() {
+        let text = "aaaaaaaa\nbbbbbbbb\n**Head**\n**Team**\ncccc\ndddd";
+        let parts = split_posts(text, 30);
+        assert_eq!(
+            parts,
+            vec!["aaaaaaaa\nbbbbbbbb", "**Head**\n**Team**\ncccc\ndddd"]
+        );
+        for part in &parts {
+            assert!(!part.lines().last().unwrap().starts_with("**"), "{part}");
+        }
+    }
+
+    #[test]
+    fn split_posts_reopens_code_blocks() {
+        let text = "```\nline one\nline two\nline three\n```";
+        let parts = split_posts(text, 24);
+        assert!(parts.len() > 1);
+        for p in parts {
+            assert!(p.starts_with("```\n"));
+            assert!(p.ends_with("\n```"));
+        }
+    }
+
+    #[test]
+    fn long_code_line_stays_in_code_blocks() {
+        let text = format!(
+            "Intro\n```\nshort\n{}\\\\end\nafter\n```\nOutro",
+            "let x = [1, 2];".repeat(4)
+        );
+        let parts = split_posts(&text, 30);
+        let code: String = parts
+            .iter()
+            .filter_map(|p| p.strip_prefix("```\n"))
+            .map(|p| p.strip_suffix("\n```").unwrap_or(p))
+            .collect();
+        assert!(code.contains(&format!("{}\\\\end", "let x = [1, 2];".repeat(4))));
+        for p in &parts {
+            assert!(p.len() <= 30, "{p}");
+            crate::validator::validate_telegram_markdown(p).unwrap();
+        }
+        assert!(parts.iter().any(|p| p.starts_with("```\nafter\n```")));
+    }
+
+    #[test]
+    fn long_line_does_not_break_links() {
+        let line = format!(
+            "{} [link text](https://example.com/a) tail",
+            "word ".repeat(8).trim_end()
+        );
+        let parts = split_posts(&line, 50);
+        assert!(parts.len() > 1);
+        assert!(
+            parts
+                .iter()
+                .any(|p| p.contains("[link text](https://example.com/a)"))
+        );
+        for p in parts {
+            crate::validator::validate_telegram_markdown(&p).unwrap();
+        }
+    }
+
+    #[test]
+    fn long_line_reopens_bold_entities() {
+        let line = format!("*{}*", "bold words here ".repeat(6).trim_end());
+        let parts = split_posts(&line, 40);
+        assert!(parts.len() > 1);
+        for p in &parts {
+            assert!(p.starts_with('*') && p.ends_with('*'), "{p}");
+            crate::validator::validate_telegram_markdown(p).unwrap();
+        }
+    }
+
+    #[test]
+    fn long_line_prefers_sentence_boundaries() {
+        let line = "First sentence here\\. Second one is longer than that";
+        let parts = split_posts(line, 40);
+        assert_eq!(parts[0], "First sentence here\\.");
+    }
+
+    #[test]
+    fn long_line_keeps_code_and_glossary_terms_whole() {
+        let line = format!(
+            "{} Rust for Linux and `let x = [1, 2];` end",
+            "word".repeat(4)
+        );
+        let glossary = Glossary {
+            terms: vec!["Rust for Linux".to_string()],
+        };
+        for limit in 20..40 {
+            let parts = split_posts_with_glossary(&line, limit, &glossary);
+            assert!(
+                parts.iter().any(|p| p.contains("Rust for Linux")),
+                "{limit}: {parts:?}"
+            );
+            assert!(
+                parts.iter().any(|p| p.contains("`let x = [1, 2];`")),
+                "{limit}: {parts:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn oversized_link_is_split_into_links() {
+        let line = format!(
+            "[{}](https://example.com)",
+            "long text ".repeat(10).trim_end()
+        );
+        let parts = split_posts(&line, 60);
+        assert!(parts.len() > 1);
+        for p in parts {
+            assert!(p.ends_with("](https://example.com)"), "{p}");
+            assert!(p.len() <= 60);
+        }
+    }
+
     #[test]
     fn generate_and_write_files() {
         let input =
@@ -1126,6 +2351,20 @@ mod tests {
         assert_eq!(formatted, "📰 **MY TITLE** 📰");
     }
 
+    #[test]
+    fn section_blocks_stay_within_limit() {
+        let section = Section {
+            title: "Long".to_string(),
+            lines: (0..400)
+                .map(|n| format!("• item {n} with some filler text"))
+                .collect(),
+            ..Section::default()
+        };
+        let blocks = section_blocks(&[section], "", &GenerationOptions::default());
+        assert!(blocks.len() > 1);
+        assert!(blocks.iter().all(|block| block.len() <= TELEGRAM_LIMIT));
+    }
+
     #[test]
     fn normalize_chat_id_trims_whitespace() {
         assert!(matches!(
@@ -0,0 +1,109 @@
+use serde::Deserialize;
+use teloxide::utils::markdown::escape;
+
+/// Terms such as crate or lint names that are published exactly as written.
+///
+/// Protected terms are not sent to the translator, keep their case in
+/// re-cased headings and are never divided when a long line is split.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Glossary {
+    pub terms: Vec<String>,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+impl Glossary {
+    /// The same glossary with every term escaped for Telegram Markdown, for
+    /// matching rendered lines.
+    pub(crate) fn escaped(&self) -> Glossary {
+        Glossary {
+            terms: self.terms.iter().map(|term| escape(term)).collect(),
+        }
+    }
+
+    /// Length of the longest term starting at byte `at` of `text`.
+    ///
+    /// Terms only match as whole words, so `serde` is not found in
+    /// `serde_json`.
+    pub fn term_at(&self, text: &str, at: usize) -> Option<usize> {
+        if text[..at].chars().next_back().is_some_and(is_word_char) {
+            return None;
+        }
+        self.terms
+            .iter()
+            .filter(|term| !term.is_empty() && text[at..].starts_with(term.as_str()))
+            .filter(|term| {
+                !text[at + term.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(is_word_char)
+            })
+            .map(String::len)
+            .max()
+    }
+
+    /// Apply `f` to the parts of `text` outside protected terms.
+    pub fn map_unprotected(&self, text: &str, f: impl Fn(&str) -> String) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut plain = 0;
+        let mut idx = 0;
+        while idx < text.len() {
+            if let Some(len) = self.term_at(text, idx) {
+                out.push_str(&f(&text[plain..idx]));
+                out.push_str(&text[idx..idx + len]);
+                idx += len;
+                plain = idx;
+            } else {
+                idx += text[idx..].chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        out.push_str(&f(&text[plain..]));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glossary() -> Glossary {
+        Glossary {
+            terms: vec![
+                "serde".to_string(),
+                "rust-analyzer".to_string(),
+                "Result<(), Uninhabited>".to_string(),
+            ],
+        }
+    }
+
+    #[test]
+    fn matches_whole_words_only() {
+        let glossary = glossary();
+        assert_eq!(glossary.term_at("use serde now", 4), Some(5));
+        assert_eq!(glossary.term_at("serde_json", 0), None);
+        assert_eq!(glossary.term_at("myserde", 2), None);
+    }
+
+    #[test]
+    fn maps_text_around_terms() {
+        let mapped = glossary().map_unprotected(
+            "rust-analyzer returns Result<(), Uninhabited> today",
+            str::to_uppercase,
+        );
+        assert_eq!(
+            mapped,
+            "rust-analyzer RETURNS Result<(), Uninhabited> TODAY"
+        );
+    }
+
+    #[test]
+    fn escaped_terms_match_rendered_lines() {
+        let escaped = glossary().escaped();
+        let line = "• rust\\-analyzer and Result<\\(\\), Uninhabited\\>";
+        assert_eq!(escaped.term_at(line, 4), Some(14));
+        assert_eq!(escaped.term_at(line, 23), Some(26));
+    }
+}
@@ -0,0 +1,291 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::{fs, path::PathBuf};
+use teloxide::utils::markdown::escape;
+
+use crate::generator::escape_markdown_url;
+use crate::parser::Section;
+use crate::shared::locale::Messages;
+
+/// Longest posting summary in characters, including the ellipsis.
+const SUMMARY_LIMIT: usize = 100;
+
+/// A link listed under the Jobs heading, e.g. a Telegram channel.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JobResource {
+    pub emoji: String,
+    pub label: String,
+    pub url: String,
+}
+
+/// Settings of the Jobs section.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JobsOptions {
+    /// Links listed under the heading; the built-in Telegram channels with
+    /// localized labels when unset.
+    pub resources: Option<Vec<JobResource>>,
+    /// Keep the link to the r/rust "Who's Hiring" thread.
+    pub reddit_thread: bool,
+    /// Reddit thread saved as JSON (the thread URL with `.json` appended)
+    /// whose top-level postings are summarised.
+    pub thread: Option<PathBuf>,
+    /// Most postings listed from `thread`.
+    pub max_postings: usize,
+}
+
+impl Default for JobsOptions {
+    fn default() -> Self {
+        Self {
+            resources: None,
+            reddit_thread: true,
+            thread: None,
+            max_postings: 10,
+        }
+    }
+}
+
+/// A top-level comment of a "Who's Hiring" thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobPosting {
+    /// Plain text, e.g. `Ferrous Systems — Full time, Berlin`.
+    pub summary: String,
+    /// Link to the comment.
+    pub url: String,
+}
+
+/// Extract the job postings from a Reddit thread saved as JSON.
+///
+/// Stickied, deleted and AutoModerator comments are skipped.
+///
+/// # Parameters
+/// - `json`: Either the whole `[post, comments]` response or the comments
+///   listing alone.
+///
+/// # Returns
+/// Postings in thread order.
+///
+/// # Errors
+/// Returns an error if `json` is not valid JSON.
+pub fn parse_reddit_thread(json: &str) -> serde_json::Result<Vec<JobPosting>> {
+    let value: Value = serde_json::from_str(json)?;
+    let listing = if value.is_array() { &value[1] } else { &value };
+    let postings = listing["data"]["children"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|child| child["kind"] == "t1")
+        .map(|child| &child["data"])
+        .filter(|data| {
+            !data["stickied"].as_bool().unwrap_or(false)
+                && data["author"] != "AutoModerator"
+                && !matches!(
+                    data["body"].as_str(),
+                    None | Some("[deleted]" | "[removed]")
+                )
+        })
+        .filter_map(|data| {
+            let summary = summarize_posting(data["body"].as_str()?)?;
+            let permalink = data["permalink"].as_str()?;
+            let url = if permalink.starts_with('/') {
+                format!("https://www.reddit.com{permalink}")
+            } else {
+                permalink.to_string()
+            };
+            Some(JobPosting { summary, url })
+        })
+        .collect();
+    Ok(postings)
+}
+
+/// Summarise a posting by its `COMPANY`, `TYPE` and `LOCATION` fields, or by
+/// its first line when it does not follow the thread template.
+fn summarize_posting(body: &str) -> Option<String> {
+    let lines: Vec<String> = body
+        .lines()
+        .map(|line| {
+            line.trim()
+                .trim_start_matches('#')
+                .replace(['*', '_'], "")
+                .trim()
+                .to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+    let field = |name: &str| {
+        lines.iter().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim().eq_ignore_ascii_case(name) && !value.trim().is_empty())
+                .then(|| value.trim().to_string())
+        })
+    };
+    let summary = match field("company") {
+        Some(company) => {
+            let details: Vec<_> = [field("type"), field("location")]
+                .into_iter()
+                .flatten()
+                .collect();
+            if details.is_empty() {
+                company
+            } else {
+                format!("{company} — {}", details.join(", "))
+            }
+        }
+        None => lines.first()?.clone(),
+    };
+    Some(if summary.chars().count() > SUMMARY_LIMIT {
+        let cut: String = summary.chars().take(SUMMARY_LIMIT - 1).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        summary
+    })
+}
+
+fn read_postings(options: &JobsOptions) -> Vec<JobPosting> {
+    let Some(path) = &options.thread else {
+        return Vec::new();
+    };
+    let parsed = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| parse_reddit_thread(&json).map_err(|e| e.to_string()));
+    match parsed {
+        Ok(mut postings) => {
+            postings.truncate(options.max_postings);
+            postings
+        }
+        Err(e) => {
+            log::warn!("Skipping job postings from {}: {e}", path.display());
+            Vec::new()
+        }
+    }
+}
+
+pub(crate) fn simplify_jobs_section(
+    section: &mut Section,
+    messages: &Messages,
+    options: &JobsOptions,
+) {
+    if section.lines.is_empty() {
+        return;
+    }
+    const PAT: &str = "Hiring thread on r/rust](";
+    let mut index = 0;
+    while index < section.lines.len() {
+        let line = &section.lines[index];
+        if let Some(start) = line.find(PAT) {
+            if !options.reddit_thread {
+                section.replace_lines(index..index + 1, Vec::new());
+                continue;
+            }
+            let url_start = start + PAT.len();
+            if let Some(rest) = line.get(url_start..)
+                && let Some(end) = rest.find(')')
+            {
+                let url = &rest[..end];
+                section.lines[index] = format!(
+                    "🦀 [{}]({})",
+                    escape(messages.job_thread),
+                    escape_markdown_url(url)
+                );
+            }
+        }
+        index += 1;
+    }
+    let mut block = jobs_resources(messages, options);
+    let postings = read_postings(options);
+    if !postings.is_empty() {
+        block.push(String::new());
+        block.extend(postings.iter().map(|posting| {
+            format!(
+                "• [{}]({})",
+                escape(&posting.summary),
+                escape_markdown_url(&posting.url)
+            )
+        }));
+    }
+    let at = 1.min(section.lines.len());
+    section.replace_lines(at..at, block);
+}
+
+fn jobs_resources(messages: &Messages, options: &JobsOptions) -> Vec<String> {
+    let render = |emoji: &str, label: &str, url: &str| {
+        format!("{emoji} [{}]({})", escape(label), escape_markdown_url(url))
+    };
+    match &options.resources {
+        Some(resources) => resources
+            .iter()
+            .map(|resource| render(&resource.emoji, &resource.label, &resource.url))
+            .collect(),
+        None => vec![
+            render("💼", messages.jobs_chat, "https://t.me/rust_jobs"),
+            render("📢", messages.jobs_feed, "https://t.me/rust_jobs_feed"),
+            render("📝", messages.jobs_hh, "https://t.me/rusthhjobs"),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sections;
+    use crate::shared::locale::Locale;
+
+    const THREAD: &str = r##"[
+  {"kind": "Listing", "data": {"children": [{"kind": "t3", "data": {"title": "Who's Hiring"}}]}},
+  {"kind": "Listing", "data": {"children": [
+    {"kind": "t1", "data": {"author": "AutoModerator", "stickied": true, "body": "Rules", "permalink": "/r/rust/comments/1/x/a/"}},
+    {"kind": "t1", "data": {"author": "ferris", "body": "**COMPANY:** Ferrous Systems\n\n**TYPE:** Full time\n\n**LOCATION:** Berlin, DE", "permalink": "/r/rust/comments/1/x/b/"}},
+    {"kind": "t1", "data": {"author": "[deleted]", "body": "[deleted]", "permalink": "/r/rust/comments/1/x/c/"}},
+    {"kind": "t1", "data": {"author": "crab", "body": "# We are hiring embedded engineers!\nApply at example.com", "permalink": "/r/rust/comments/1/x/d/"}},
+    {"kind": "more", "data": {"count": 3}}
+  ]}}
+]"##;
+
+    #[test]
+    fn parses_top_level_postings() {
+        let postings = parse_reddit_thread(THREAD).unwrap();
+        assert_eq!(
+            postings,
+            vec![
+                JobPosting {
+                    summary: "Ferrous Systems — Full time, Berlin, DE".into(),
+                    url: "https://www.reddit.com/r/rust/comments/1/x/b/".into(),
+                },
+                JobPosting {
+                    summary: "We are hiring embedded engineers!".into(),
+                    url: "https://www.reddit.com/r/rust/comments/1/x/d/".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn long_summaries_are_shortened() {
+        let summary = summarize_posting(&"word ".repeat(40)).unwrap();
+        assert_eq!(summary.chars().count(), SUMMARY_LIMIT);
+        assert!(summary.ends_with("word…"));
+    }
+
+    #[test]
+    fn custom_resources_replace_the_thread_link() {
+        let mut section = parse_sections(
+            "## Jobs\n\nPlease see the latest [Who's Hiring thread on r/rust](https://www.reddit.com/r/rust/comments/1/)\n",
+        )
+        .remove(0);
+        let options = JobsOptions {
+            resources: Some(vec![JobResource {
+                emoji: "🧑‍💻".into(),
+                label: "Rust jobs in Berlin".into(),
+                url: "https://t.me/rust_berlin_jobs".into(),
+            }]),
+            reddit_thread: false,
+            ..JobsOptions::default()
+        };
+        simplify_jobs_section(&mut section, Locale::En.messages(), &options);
+        assert_eq!(
+            section.lines,
+            vec!["🧑‍💻 [Rust jobs in Berlin](https://t.me/rust_berlin_jobs)"]
+        );
+    }
+}
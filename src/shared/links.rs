@@ -0,0 +1,362 @@
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use reqwest::{StatusCode, blocking::Client};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+/// Query parameters that only serve to track readers.
+pub const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "msclkid", "mc_cid", "mc_eid", "igshid", "ref_src", "_hsenc",
+    "_hsmi", "mkt_tok",
+];
+
+/// Timeout applied to each online link check.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Return `true` when the query parameter `name` is used for tracking.
+pub fn is_tracking_param(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.starts_with("utm_") || TRACKING_PARAMS.contains(&name.as_str())
+}
+
+/// A link found in a generated post.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostLink {
+    /// Zero-based index of the post containing the link.
+    pub post: usize,
+    pub text: String,
+    pub url: String,
+}
+
+/// How serious a [`LinkProblem`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// Something wrong or suspicious about a link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkProblem {
+    /// The URL cannot be a valid web address.
+    Malformed(String),
+    /// Parentheses do not pair up, usually a sign of broken escaping.
+    UnbalancedParens,
+    /// Plain `http://` link.
+    Insecure,
+    /// Query parameters such as `utm_source`.
+    Tracking(Vec<String>),
+    /// Same URL already linked in the given post.
+    Duplicate { first_post: usize },
+}
+
+impl LinkProblem {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::Malformed(_) | Self::UnbalancedParens => Severity::Error,
+            Self::Insecure | Self::Tracking(_) | Self::Duplicate { .. } => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for LinkProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(reason) => write!(f, "malformed URL: {reason}"),
+            Self::UnbalancedParens => write!(f, "unbalanced parentheses"),
+            Self::Insecure => write!(f, "insecure http:// link"),
+            Self::Tracking(params) => write!(f, "tracking parameters: {}", params.join(", ")),
+            Self::Duplicate { first_post } => {
+                write!(f, "duplicate of a link in post {}", first_post + 1)
+            }
+        }
+    }
+}
+
+/// A problem found for a specific link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkReport {
+    pub link: PostLink,
+    pub problem: LinkProblem,
+}
+
+/// Collect every inline link from Telegram Markdown `posts`.
+///
+/// # Returns
+/// The links in post order with escapes removed from text and URL.
+pub fn extract_links(posts: &[String]) -> Vec<PostLink> {
+    let mut links = Vec::new();
+    for (post, text) in posts.iter().enumerate() {
+        let mut current: Option<PostLink> = None;
+        for event in Parser::new(text) {
+            match event {
+                Event::Start(Tag::Link { dest_url, .. }) => {
+                    current = Some(PostLink {
+                        post,
+                        text: String::new(),
+                        url: dest_url.to_string(),
+                    });
+                }
+                Event::End(TagEnd::Link) => links.extend(current.take()),
+                Event::Text(t) | Event::Code(t) => {
+                    if let Some(link) = current.as_mut() {
+                        link.text.push_str(&t);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    links
+}
+
+/// Return why `url` is not a well-formed web address, if it is not.
+fn syntax_error(url: &str) -> Option<String> {
+    if url.is_empty() {
+        return Some("empty".to_string());
+    }
+    if let Some(ch) = url.chars().find(|c| c.is_whitespace() || c.is_control()) {
+        return Some(format!("contains {ch:?}"));
+    }
+    if url.contains('\\') {
+        return Some("contains a backslash left over from escaping".to_string());
+    }
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return Some("scheme is not http or https".to_string());
+    };
+    let host = rest
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default()
+        .rsplit('@')
+        .next()
+        .unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default();
+    if host.is_empty() {
+        return Some("missing host".to_string());
+    }
+    if !host
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '.')
+        || host.starts_with('.')
+        || host.contains("..")
+    {
+        return Some(format!("invalid host {host:?}"));
+    }
+    None
+}
+
+/// Names of the tracking parameters in the query of `url`.
+fn tracking_params(url: &str) -> Vec<String> {
+    let Some((_, query)) = url.split_once('?') else {
+        return Vec::new();
+    };
+    let query = query.split('#').next().unwrap_or_default();
+    query
+        .split('&')
+        .map(|pair| pair.split('=').next().unwrap_or_default())
+        .filter(|name| is_tracking_param(name))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Check the syntax of `links` and look for suspicious patterns.
+///
+/// # Returns
+/// One report per problem, in link order.
+pub fn lint_links(links: &[PostLink]) -> Vec<LinkReport> {
+    let mut reports = Vec::new();
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for link in links {
+        let mut report = |problem| {
+            reports.push(LinkReport {
+                link: link.clone(),
+                problem,
+            })
+        };
+        if let Some(reason) = syntax_error(&link.url) {
+            report(LinkProblem::Malformed(reason));
+        }
+        if link.url.matches('(').count() != link.url.matches(')').count() {
+            report(LinkProblem::UnbalancedParens);
+        }
+        if link.url.starts_with("http://") {
+            report(LinkProblem::Insecure);
+        }
+        let params = tracking_params(&link.url);
+        if !params.is_empty() {
+            report(LinkProblem::Tracking(params));
+        }
+        match seen.get(link.url.as_str()) {
+            Some(&first_post) => report(LinkProblem::Duplicate { first_post }),
+            None => {
+                seen.insert(&link.url, link.post);
+            }
+        }
+    }
+    reports
+}
+
+/// Point `url` at `base`, keeping its path, query and fragment.
+///
+/// Used to run the online check against a local server.
+pub fn rebase_url(url: &str, base: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let path = rest.find(['/', '?', '#']).map_or("", |idx| &rest[idx..]);
+    format!("{}{}", base.trim_end_matches('/'), path)
+}
+
+/// Outcome of requesting a link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkStatus {
+    pub url: String,
+    /// HTTP status, or the transport error.
+    pub result: Result<u16, String>,
+}
+
+impl LinkStatus {
+    /// `true` for 2xx responses; redirects are followed by the client.
+    pub fn is_ok(&self) -> bool {
+        self.result
+            .as_ref()
+            .is_ok_and(|status| (200..300).contains(status))
+    }
+}
+
+fn request(client: &Client, url: &str) -> Result<u16, String> {
+    let status = client.head(url).send().map_err(|e| e.to_string())?.status();
+    if status == StatusCode::METHOD_NOT_ALLOWED || status == StatusCode::NOT_IMPLEMENTED {
+        return client
+            .get(url)
+            .send()
+            .map(|resp| resp.status().as_u16())
+            .map_err(|e| e.to_string());
+    }
+    Ok(status.as_u16())
+}
+
+/// Send a `HEAD` request to each of `urls`, falling back to `GET` when the
+/// server does not support `HEAD`.
+///
+/// # Parameters
+/// - `urls`: Links to check; each is requested once.
+/// - `concurrency`: Maximum number of requests in flight.
+/// - `base`: Optional base replacing scheme and host, see [`rebase_url`].
+///
+/// # Returns
+/// The status of every URL in input order.
+pub fn check_links_online(
+    urls: &[String],
+    concurrency: usize,
+    base: Option<&str>,
+) -> Vec<LinkStatus> {
+    let client = Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_else(|_| Client::new());
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<LinkStatus>>> = Mutex::new(vec![None; urls.len()]);
+    thread::scope(|scope| {
+        for _ in 0..concurrency.clamp(1, urls.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(url) = urls.get(index) else {
+                        break;
+                    };
+                    let target = base.map_or_else(|| url.clone(), |base| rebase_url(url, base));
+                    log::debug!("Checking {target}");
+                    let status = LinkStatus {
+                        url: url.clone(),
+                        result: request(&client, &target),
+                    };
+                    results.lock().expect("results lock poisoned")[index] = Some(status);
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .expect("results lock poisoned")
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(post: usize, url: &str) -> PostLink {
+        PostLink {
+            post,
+            text: String::new(),
+            url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn extracts_unescaped_links() {
+        let posts = vec![
+            "Read [Rust \\(2024\\)](https://example.com/a_\\(b\\)) now\\.".to_string(),
+            "*Part 2/2*\n• [B](https://b.example/)".to_string(),
+        ];
+        let links = extract_links(&posts);
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].text, "Rust (2024)");
+        assert_eq!(links[0].url, "https://example.com/a_(b)");
+        assert_eq!(links[1].post, 1);
+    }
+
+    #[test]
+    fn reports_suspicious_links() {
+        let links = vec![
+            link(0, "http://example.com/?utm_source=twir&id=1"),
+            link(0, "https://example.com/a_(b"),
+            link(1, "https://exa mple.com/"),
+            link(1, "http://example.com/?utm_source=twir&id=1"),
+            link(1, "ftp://example.com/"),
+        ];
+        let problems: Vec<(usize, LinkProblem)> = lint_links(&links)
+            .into_iter()
+            .map(|r| (links.iter().position(|l| *l == r.link).unwrap(), r.problem))
+            .collect();
+        assert!(problems.contains(&(0, LinkProblem::Insecure)));
+        assert!(problems.contains(&(0, LinkProblem::Tracking(vec!["utm_source".into()]))));
+        assert!(problems.contains(&(1, LinkProblem::UnbalancedParens)));
+        assert!(matches!(
+            problems.iter().find(|p| p.0 == 2),
+            Some((_, LinkProblem::Malformed(_)))
+        ));
+        assert!(problems.contains(&(3, LinkProblem::Duplicate { first_post: 0 })));
+        assert!(matches!(
+            problems.last(),
+            Some((4, LinkProblem::Malformed(_)))
+        ));
+    }
+
+    #[test]
+    fn rebases_onto_local_server() {
+        assert_eq!(
+            rebase_url(
+                "https://blog.rust-lang.org/2025/a?x=1#y",
+                "http://127.0.0.1:8080/"
+            ),
+            "http://127.0.0.1:8080/2025/a?x=1#y"
+        );
+        assert_eq!(
+            rebase_url("https://example.com", "http://localhost"),
+            "http://localhost"
+        );
+    }
+}
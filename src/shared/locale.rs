@@ -0,0 +1,195 @@
+use phf::phf_map;
+use serde::Deserialize;
+
+/// Language of the text the generator adds to posts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ru,
+}
+
+/// Strings inserted by the generator, as plain text.
+#[derive(Debug)]
+pub struct Messages {
+    /// Prefix of the `Part X/Y` marker on follow-up posts.
+    pub part: &'static str,
+    pub view_web_version: &'static str,
+    pub job_thread: &'static str,
+    pub jobs_chat: &'static str,
+    pub jobs_feed: &'static str,
+    pub jobs_hh: &'static str,
+    pub no_cfp_tasks: &'static str,
+    pub instructions: &'static str,
+    /// First line of the standalone Final Comment Period post.
+    pub fcp_intro: &'static str,
+    /// Introduces the teams without Final Comment Period items.
+    pub fcp_quiet: &'static str,
+    pub online: &'static str,
+    pub hybrid: &'static str,
+    /// Performance regression count; `{}` is replaced by the count.
+    pub regressions: &'static str,
+    /// Performance improvement count; `{}` is replaced by the count.
+    pub improvements: &'static str,
+    /// Mixed performance change count; `{}` is replaced by the count.
+    pub mixed: &'static str,
+    pub in_rollups: &'static str,
+    pub mean: &'static str,
+    pub range: &'static str,
+    pub by: &'static str,
+    pub full_report: &'static str,
+    /// Heading of the table of contents.
+    pub contents: &'static str,
+    /// Merged pull request total; `{}` is replaced by the count.
+    pub prs_merged: &'static str,
+    /// Size of a collapsed pull request category; `{}` is the count.
+    pub pull_requests: &'static str,
+    pub see_web_version: &'static str,
+    /// Replaces list items cut by `[[sections.truncate]]`; `{}` is the
+    /// number of hidden items.
+    pub more_items: &'static str,
+    /// Translated section and subheading titles keyed by lowercase English
+    /// title.
+    pub titles: &'static phf::Map<&'static str, &'static str>,
+}
+
+static EN_TITLES: phf::Map<&'static str, &'static str> = phf_map! {};
+
+static RU_TITLES: phf::Map<&'static str, &'static str> = phf_map! {
+    "updates from rust community" => "Новости сообщества Rust",
+    "official" => "Официально",
+    "foundation" => "Фонд",
+    "newsletters" => "Рассылки",
+    "project/tooling updates" => "Обновления проектов и инструментов",
+    "observations/thoughts" => "Наблюдения и размышления",
+    "rust walkthroughs" => "Разборы Rust",
+    "research" => "Исследования",
+    "miscellaneous" => "Разное",
+    "crate of the week" => "Крейт недели",
+    "calls for testing" => "Призывы к тестированию",
+    "call for participation; projects and speakers" => "Призыв к участию: проекты и докладчики",
+    "cfp - projects" => "CFP - проекты",
+    "cfp - events" => "CFP - мероприятия",
+    "updates from the rust project" => "Новости проекта Rust",
+    "rust compiler performance triage" => "Производительность компилятора Rust",
+    "approved rfcs" => "Одобренные RFC",
+    "final comment period" => "Последний период обсуждения",
+    "tracking issues & prs" => "Отслеживаемые задачи и PR",
+    "new and updated rfcs" => "Новые и обновлённые RFC",
+    "upcoming events" => "Предстоящие события",
+    "virtual" => "Онлайн",
+    "africa" => "Африка",
+    "asia" => "Азия",
+    "europe" => "Европа",
+    "north america" => "Северная Америка",
+    "oceania" => "Океания",
+    "south america" => "Южная Америка",
+    "jobs" => "Вакансии",
+    "quote of the week" => "Цитата недели",
+};
+
+static EN: Messages = Messages {
+    part: "Part",
+    view_web_version: "View web version",
+    job_thread: "Rust Job Reddit Thread",
+    jobs_chat: "Rust Jobs chat",
+    jobs_feed: "Rust Jobs feed",
+    jobs_hh: "Rust HH jobs",
+    no_cfp_tasks: "No new tasks this week.",
+    instructions: "Instructions",
+    fcp_intro: "What's being decided this week:",
+    fcp_quiet: "No new items this week:",
+    online: "online",
+    hybrid: "hybrid",
+    regressions: "{} regressions",
+    improvements: "{} improvements",
+    mixed: "{} mixed",
+    in_rollups: "in rollups",
+    mean: "mean",
+    range: "range",
+    by: "by",
+    full_report: "full report",
+    contents: "Contents",
+    prs_merged: "{} PRs merged",
+    pull_requests: "{} pull requests",
+    see_web_version: "see web version",
+    more_items: "…and {} more",
+    titles: &EN_TITLES,
+};
+
+static RU: Messages = Messages {
+    part: "Часть",
+    view_web_version: "Веб-версия выпуска",
+    job_thread: "Ветка вакансий Rust на Reddit",
+    jobs_chat: "Чат вакансий Rust",
+    jobs_feed: "Лента вакансий Rust",
+    jobs_hh: "Вакансии Rust с HH",
+    no_cfp_tasks: "На этой неделе новых задач нет.",
+    instructions: "Инструкции",
+    fcp_intro: "Что решается на этой неделе:",
+    fcp_quiet: "Без новых пунктов на этой неделе:",
+    online: "онлайн",
+    hybrid: "гибрид",
+    regressions: "регрессии: {}",
+    improvements: "улучшения: {}",
+    mixed: "смешанные: {}",
+    in_rollups: "в роллапах",
+    mean: "среднее",
+    range: "диапазон",
+    by: "автор",
+    full_report: "полный отчёт",
+    contents: "Содержание",
+    prs_merged: "Принято PR: {}",
+    pull_requests: "PR: {}",
+    see_web_version: "см. веб-версию",
+    more_items: "…и ещё {}",
+    titles: &RU_TITLES,
+};
+
+impl Locale {
+    /// Message catalog of this locale.
+    pub fn messages(self) -> &'static Messages {
+        match self {
+            Locale::En => &EN,
+            Locale::Ru => &RU,
+        }
+    }
+}
+
+impl Messages {
+    /// Translate a section or subheading title.
+    ///
+    /// # Returns
+    /// The translation, or `title` itself when the catalog has none.
+    pub fn title<'a>(&self, title: &'a str) -> &'a str {
+        self.titles
+            .get(title.trim().to_lowercase().as_str())
+            .copied()
+            .unwrap_or(title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn titles_are_translated_case_insensitively() {
+        let ru = Locale::Ru.messages();
+        assert_eq!(ru.title("Crate of the Week"), "Крейт недели");
+        assert_eq!(ru.title("UPCOMING EVENTS"), "Предстоящие события");
+        assert_eq!(ru.title("Something New"), "Something New");
+        assert_eq!(Locale::En.messages().title("Jobs"), "Jobs");
+    }
+
+    #[test]
+    fn locale_is_read_from_config() {
+        #[derive(Deserialize)]
+        struct Config {
+            locale: Locale,
+        }
+        let config: Config = toml::from_str("locale = \"ru\"").unwrap();
+        assert_eq!(config.locale, Locale::Ru);
+    }
+}
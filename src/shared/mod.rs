@@ -1,3 +1,28 @@
+pub mod calendar;
+pub mod card;
+pub mod config;
+pub mod cover;
+pub mod crates;
+pub mod events;
+pub mod export;
+pub mod fcp;
 pub mod generator_shared;
+pub mod glossary;
+pub mod jobs;
+pub mod links;
+pub mod locale;
+pub mod packing;
 pub mod parser;
+pub mod perf;
+pub mod pulls;
+pub mod quote;
+pub mod rules;
+pub mod sections;
+pub mod tags;
+pub mod theme;
+pub mod threading;
+pub mod toc;
+pub mod transforms;
+pub mod translate;
+pub mod urls;
 pub mod validator;
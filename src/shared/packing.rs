@@ -0,0 +1,169 @@
+use serde::Deserialize;
+
+/// Strategy used to distribute rendered sections across Telegram posts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum PackingStrategy {
+    /// Append whole sections until the limit is reached.
+    #[default]
+    Greedy,
+    /// Use the fewest posts and keep their sizes as even as possible.
+    Balanced,
+}
+
+/// Pack `blocks` into the fewest posts with the most even sizes.
+///
+/// Every block is kept intact; a block that exceeds `limit` on its own must
+/// be split before calling this function. Among packings with the minimal
+/// number of posts the one with the smallest sum of squared free space wins,
+/// which favours posts of similar length.
+///
+/// # Parameters
+/// - `blocks`: Rendered text fragments in publication order.
+/// - `limit`: Maximum length of a post.
+/// - `keep_last_attached`: Forbid the final block from forming a post alone.
+///
+/// # Returns
+/// The packed posts in order, or `None` when no packing exists.
+pub(crate) fn pack_balanced(
+    blocks: &[String],
+    limit: usize,
+    keep_last_attached: bool,
+) -> Option<Vec<String>> {
+    let breaks = balanced_breaks(blocks, limit, keep_last_attached)
+        .or_else(|| balanced_breaks(blocks, limit, false))?;
+
+    Some(
+        breaks
+            .windows(2)
+            .map(|range| blocks[range[0]..range[1]].concat())
+            .collect(),
+    )
+}
+
+/// Compute the block indices at which posts start, followed by `blocks.len()`.
+fn balanced_breaks(
+    blocks: &[String],
+    limit: usize,
+    keep_last_attached: bool,
+) -> Option<Vec<usize>> {
+    let count = blocks.len();
+    // best[end] = (posts, squared slack, start of the last post) for blocks[..end]
+    let mut best: Vec<Option<(usize, u64, usize)>> = vec![None; count + 1];
+    best[0] = Some((0, 0, 0));
+
+    for end in 1..=count {
+        let mut len = 0usize;
+        for start in (0..end).rev() {
+            len += blocks[start].len();
+            if len > limit && start + 1 < end {
+                break;
+            }
+            if keep_last_attached && count > 1 && end == count && start + 1 == count {
+                continue;
+            }
+            let Some((posts, cost, _)) = best[start] else {
+                continue;
+            };
+            let slack = limit.saturating_sub(len) as u64;
+            let candidate = (posts + 1, cost + slack * slack, start);
+            if best[end].is_none_or(|(p, c, _)| (candidate.0, candidate.1) < (p, c)) {
+                best[end] = Some(candidate);
+            }
+        }
+    }
+
+    best[count]?;
+    let mut breaks = vec![count];
+    let mut end = count;
+    while end > 0 {
+        let (_, _, start) = best[end]?;
+        breaks.push(start);
+        end = start;
+    }
+    breaks.reverse();
+    Some(breaks)
+}
+
+/// Ensure the final post is not just `footer`.
+///
+/// The footer is merged into the previous post when it fits; otherwise the
+/// last line of the previous post moves forward to accompany it.
+pub(crate) fn keep_footer_attached(
+    mut posts: Vec<String>,
+    footer: &str,
+    limit: usize,
+) -> Vec<String> {
+    if posts.len() < 2 || posts.last().is_none_or(|last| last.trim() != footer.trim()) {
+        return posts;
+    }
+    let footer_post = posts.pop().unwrap_or_default();
+    let previous = posts.pop().unwrap_or_default();
+
+    if previous.len() + footer_post.len() <= limit {
+        posts.push(previous + &footer_post);
+        return posts;
+    }
+
+    let head = previous.trim_end_matches('\n');
+    match head.rfind('\n') {
+        Some(idx) if !head[idx..].contains("```") && !head[..idx].trim().is_empty() => {
+            posts.push(format!("{}\n", &head[..idx]));
+            posts.push(format!("{}\n{footer_post}", &head[idx + 1..]));
+        }
+        _ => {
+            posts.push(previous);
+            posts.push(footer_post);
+        }
+    }
+    posts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks(sizes: &[usize]) -> Vec<String> {
+        sizes.iter().map(|size| "a".repeat(*size)).collect()
+    }
+
+    #[test]
+    fn balanced_uses_minimal_post_count() {
+        let posts = pack_balanced(&blocks(&[40, 40, 40, 10]), 100, false).unwrap();
+        assert_eq!(posts.len(), 2);
+    }
+
+    #[test]
+    fn balanced_evens_out_sizes() {
+        let posts = pack_balanced(&blocks(&[30, 30, 30, 30, 10]), 100, false).unwrap();
+        let sizes: Vec<usize> = posts.iter().map(String::len).collect();
+        assert_eq!(sizes, vec![60, 70]);
+    }
+
+    #[test]
+    fn last_block_is_not_left_alone() {
+        let posts = pack_balanced(&blocks(&[50, 50, 5]), 100, true).unwrap();
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[1].len(), 55);
+    }
+
+    #[test]
+    fn footer_merges_into_previous_post() {
+        let posts = vec![
+            "a\n".to_string(),
+            "b\n".to_string(),
+            "\nfooter\n".to_string(),
+        ];
+        let packed = keep_footer_attached(posts, "\nfooter\n", 100);
+        assert_eq!(packed, vec!["a\n", "b\n\nfooter\n"]);
+    }
+
+    #[test]
+    fn footer_pulls_last_line_when_previous_is_full() {
+        let previous = format!("{}\nlast line\n", "x".repeat(20));
+        let posts = vec![previous, "\nfooter\n".to_string()];
+        let packed = keep_footer_attached(posts, "\nfooter\n", 32);
+        assert_eq!(packed.len(), 2);
+        assert_eq!(packed[1], "last line\n\nfooter\n");
+    }
+}
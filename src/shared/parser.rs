@@ -1,17 +1,116 @@
-use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use pulldown_cmark::{BrokenLink, Event, HeadingLevel, LinkType, Options, Parser, Tag, TagEnd};
 
 use crate::generator::{escape_markdown_url, format_subheading};
-use teloxide::utils::markdown::escape;
+use crate::shared::urls::UrlOptions;
+use teloxide::utils::markdown::{escape, escape_code};
 
 /// Representation of a single TWIR section.
 #[derive(Default)]
 pub struct Section {
     pub title: String,
     pub lines: Vec<String>,
+    /// H1, H3 and H4 headings found inside the section, in order.
+    pub subheadings: Vec<Subheading>,
 }
 
-fn fix_bare_link(line: &str) -> String {
-    if line.contains("](") {
+/// A heading nested inside a [`Section`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subheading {
+    /// Plain heading text without link markup.
+    pub title: String,
+    /// Markdown heading level (1, 3 or 4).
+    pub level: u8,
+    /// Index of the rendered heading in [`Section::lines`].
+    pub line: usize,
+}
+
+/// Content the parser could not turn into post text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseReport {
+    /// Labels of `[text][label]` references without a definition.
+    pub broken_references: Vec<String>,
+    /// Raw HTML fragments left out of the posts.
+    pub dropped_html: Vec<String>,
+}
+
+/// Inline HTML tags whose content is kept as plain text.
+const TRANSPARENT_TAGS: &[&str] = &[
+    "abbr", "b", "br", "em", "i", "kbd", "mark", "small", "span", "strong", "sub", "sup", "u",
+];
+
+/// Name of the tag starting `fragment`, e.g. `small` for `</small>`.
+fn html_tag_name(fragment: &str) -> Option<String> {
+    let rest = fragment.trim_start().strip_prefix('<')?;
+    let rest = rest.strip_prefix('/').unwrap_or(rest);
+    let name: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    (!name.is_empty()).then(|| name.to_ascii_lowercase())
+}
+
+/// Return `true` for a transparent tag or a comment, which are never reported.
+fn is_harmless_html(fragment: &str) -> bool {
+    fragment.trim_start().starts_with("<!--")
+        || html_tag_name(fragment).is_some_and(|name| TRANSPARENT_TAGS.contains(&name.as_str()))
+}
+
+/// Text of an HTML block made of transparent tags, comments and text.
+///
+/// # Returns
+/// `None` when the block contains any other tag.
+fn transparent_html_text(block: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut rest = block;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let end = if tail.starts_with("<!--") {
+            tail.find("-->").map(|idx| idx + 3)
+        } else {
+            tail.find('>').map(|idx| idx + 1)
+        }?;
+        if !is_harmless_html(&tail[..end]) {
+            return None;
+        }
+        rest = &tail[end..];
+    }
+    text.push_str(rest);
+    Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+impl Section {
+    /// Range of `lines` belonging to the subheading at `index`, excluding the
+    /// heading line itself.
+    ///
+    /// A subsection extends until the next heading of the same or a higher
+    /// level, or until the end of the section.
+    pub fn subsection_range(&self, index: usize) -> std::ops::Range<usize> {
+        let heading = &self.subheadings[index];
+        let end = self.subheadings[index + 1..]
+            .iter()
+            .find(|next| next.level <= heading.level)
+            .map_or(self.lines.len(), |next| next.line);
+        (heading.line + 1).min(end)..end
+    }
+
+    /// Replace `range` of `lines` with `replacement`, dropping subheadings
+    /// inside the range and shifting the ones after it.
+    pub fn replace_lines(&mut self, range: std::ops::Range<usize>, replacement: Vec<String>) {
+        let added = replacement.len();
+        let removed = range.len();
+        self.lines.splice(range.clone(), replacement);
+        self.subheadings.retain(|sub| !range.contains(&sub.line));
+        for sub in &mut self.subheadings {
+            if sub.line >= range.end {
+                sub.line = sub.line + added - removed;
+            }
+        }
+    }
+}
+
+fn fix_bare_link(line: &str, urls: &UrlOptions) -> String {
+    if line.contains("](") || line.contains('`') {
         return line.to_string();
     }
     let plain = line.replace('\\', "");
@@ -23,12 +122,17 @@ fn fix_bare_link(line: &str) -> String {
     {
         let url = &trimmed[start + 1..trimmed.len() - 1];
         let text = trimmed[..start].trim_end();
-        return format!("[{}]({})", escape(text), escape_markdown_url(url));
+        return format!(
+            "[{}]({})",
+            escape(text),
+            escape_markdown_url(&urls.rewrite(url))
+        );
     }
     line.to_string()
 }
 
-fn replace_github_mentions(text: &str) -> String {
+/// Link `@user` mentions to GitHub profiles, rewritten by `urls`.
+fn replace_github_mentions(text: &str, urls: &UrlOptions) -> String {
     let mut result = String::new();
     let chars: Vec<char> = text.chars().collect();
     let mut i = 0;
@@ -36,6 +140,19 @@ fn replace_github_mentions(text: &str) -> String {
     while i < chars.len() {
         let ch = chars[i];
         match ch {
+            '\\' => {
+                result.extend(chars[i..chars.len().min(i + 2)].iter());
+                i += 2;
+            }
+            '`' => {
+                let mut end = i + 1;
+                while end < chars.len() && chars[end] != '`' {
+                    end += if chars[end] == '\\' { 2 } else { 1 };
+                }
+                let end = (end + 1).min(chars.len());
+                result.extend(chars[i..end].iter());
+                i = end;
+            }
             '[' => {
                 bracket_depth += 1;
                 result.push(ch);
@@ -58,7 +175,8 @@ fn replace_github_mentions(text: &str) -> String {
                         && !next.is_some_and(|c| c.is_ascii_alphanumeric() || c == '-')
                     {
                         let user: String = chars[i + 1..j].iter().collect();
-                        result.push_str(&format!("[{user}](https://github.com/{user})"));
+                        let url = urls.rewrite(&format!("https://github.com/{user}"));
+                        result.push_str(&format!("[{user}]({})", escape_markdown_url(&url)));
                         i = j;
                         continue;
                     }
@@ -206,7 +324,7 @@ fn normalize_compact_tables(text: &str) -> String {
     normalized
 }
 
-fn unescape_telegram_markdown(text: &str) -> String {
+pub(crate) fn unescape_telegram_markdown(text: &str) -> String {
     let mut out = String::with_capacity(text.len());
     let mut chars = text.chars();
     while let Some(ch) = chars.next() {
@@ -291,17 +409,59 @@ fn render_table_as_code_block(table: &[Vec<String>]) -> Option<String> {
 /// # Returns
 /// A list of [`Section`]s preserving the order found in the input.
 pub fn parse_sections(text: &str) -> Vec<Section> {
+    parse_sections_with(text, &UrlOptions::default())
+}
+
+/// Parse TWIR Markdown into sections, rewriting every link with `urls`.
+///
+/// # Parameters
+/// - `text`: Full Markdown source from a TWIR issue.
+/// - `urls`: Tracking parameter, host and regex rewrites for links.
+///
+/// # Returns
+/// A list of [`Section`]s preserving the order found in the input.
+pub fn parse_sections_with(text: &str, urls: &UrlOptions) -> Vec<Section> {
+    parse_sections_report(text, urls).0
+}
+
+/// Parse TWIR Markdown into sections and report what could not be rendered.
+///
+/// Full and collapsed references without a definition are listed as broken;
+/// shortcut forms such as `[video]` are ordinary text in TWIR. `<small>`,
+/// `<sub>` and similar tags are dropped while their content is kept as plain
+/// text; any other raw HTML is left out and listed in the report.
+///
+/// # Parameters
+/// - `text`: Full Markdown source from a TWIR issue.
+/// - `urls`: Tracking parameter, host and regex rewrites for links.
+///
+/// # Returns
+/// The sections and the [`ParseReport`] for the issue.
+pub fn parse_sections_report(text: &str, urls: &UrlOptions) -> (Vec<Section>, ParseReport) {
     let mut sections = Vec::new();
     let mut current: Option<Section> = None;
     let mut buffer = String::new();
+    let mut report = ParseReport::default();
+    let mut broken_references = Vec::new();
+    let mut html_block: Option<String> = None;
     let normalized = normalize_compact_tables(text);
-    let parser = Parser::new_ext(&normalized, Options::ENABLE_TABLES);
+    let parser = Parser::new_with_broken_link_callback(
+        &normalized,
+        Options::ENABLE_TABLES,
+        Some(|link: BrokenLink<'_>| {
+            if link.link_type != LinkType::Shortcut {
+                broken_references.push(link.reference.to_string());
+            }
+            None
+        }),
+    );
     let mut link_dest: Option<String> = None;
     let mut list_depth: usize = 0;
     let mut in_code_block = false;
     let mut in_heading = false;
     let mut heading_raw = String::new();
     let mut heading_sanitized = String::new();
+    let mut heading_text = String::new();
     let mut table: Vec<Vec<String>> = Vec::new();
     let mut row: Vec<String> = Vec::new();
     for event in parser {
@@ -310,6 +470,7 @@ pub fn parse_sections(text: &str) -> Vec<Section> {
                 in_heading = true;
                 heading_raw.clear();
                 heading_sanitized.clear();
+                heading_text.clear();
                 if level == HeadingLevel::H2 {
                     if let Some(ref mut sec) = current {
                         let line = buffer.trim();
@@ -340,7 +501,7 @@ pub fn parse_sections(text: &str) -> Vec<Section> {
                 if level == HeadingLevel::H2 {
                     current = Some(Section {
                         title: raw.to_string(),
-                        lines: Vec::new(),
+                        ..Section::default()
                     });
                     buffer.clear();
                 } else if matches!(
@@ -350,13 +511,18 @@ pub fn parse_sections(text: &str) -> Vec<Section> {
                     if let Some(ref mut sec) = current
                         && !raw.is_empty()
                     {
+                        sec.subheadings.push(Subheading {
+                            title: heading_text.trim().to_string(),
+                            level: level as u8,
+                            line: sec.lines.len(),
+                        });
                         sec.lines.push(format_subheading(raw));
                     }
                     buffer.clear();
                 } else if let Some(ref mut sec) = current {
                     let sanitized = heading_sanitized.trim();
                     if !sanitized.is_empty() {
-                        let fixed = replace_github_mentions(&fix_bare_link(sanitized));
+                        let fixed = replace_github_mentions(&fix_bare_link(sanitized, urls), urls);
                         sec.lines.push(fixed);
                     }
                     buffer.clear();
@@ -365,12 +531,13 @@ pub fn parse_sections(text: &str) -> Vec<Section> {
                 }
                 heading_raw.clear();
                 heading_sanitized.clear();
+                heading_text.clear();
             }
             Event::Start(Tag::List(_)) => {
                 if let Some(ref mut sec) = current {
                     let line = buffer.trim_end();
                     if !line.is_empty() {
-                        let fixed = replace_github_mentions(&fix_bare_link(line));
+                        let fixed = replace_github_mentions(&fix_bare_link(line, urls), urls);
                         let indent = "  ".repeat(list_depth.saturating_sub(1));
                         sec.lines.push(format!("{indent}• {fixed}"));
                         buffer.clear();
@@ -388,7 +555,7 @@ pub fn parse_sections(text: &str) -> Vec<Section> {
                 if let Some(ref mut sec) = current {
                     let line = buffer.trim_end();
                     if !line.is_empty() {
-                        let fixed = replace_github_mentions(&fix_bare_link(line));
+                        let fixed = replace_github_mentions(&fix_bare_link(line, urls), urls);
                         let indent = "  ".repeat(list_depth.saturating_sub(1));
                         sec.lines.push(format!("{indent}• {fixed}"));
                     }
@@ -442,7 +609,7 @@ pub fn parse_sections(text: &str) -> Vec<Section> {
                 } else if let Some(ref mut sec) = current {
                     let line = buffer.trim_end();
                     if !line.is_empty() {
-                        let fixed = replace_github_mentions(&fix_bare_link(line));
+                        let fixed = replace_github_mentions(&fix_bare_link(line, urls), urls);
                         sec.lines.push(fixed);
                     }
                     buffer.clear();
@@ -457,7 +624,7 @@ pub fn parse_sections(text: &str) -> Vec<Section> {
                 } else {
                     buffer.push('[');
                 }
-                link_dest = Some(dest_url.to_string());
+                link_dest = Some(urls.rewrite(&dest_url));
             }
             Event::End(TagEnd::Link) => {
                 if let Some(d) = link_dest.take() {
@@ -495,10 +662,16 @@ pub fn parse_sections(text: &str) -> Vec<Section> {
                 }
                 buffer.push_str("```");
             }
+            Event::Code(t) if !in_heading && link_dest.is_none() => {
+                buffer.push('`');
+                buffer.push_str(&escape_code(&t));
+                buffer.push('`');
+            }
             Event::Text(t) | Event::Code(t) => {
                 if in_heading {
                     heading_raw.push_str(&t);
                     heading_sanitized.push_str(&escape(&t));
+                    heading_text.push_str(&t);
                 } else if in_code_block {
                     buffer.push_str(&t);
                 } else {
@@ -511,27 +684,46 @@ pub fn parse_sections(text: &str) -> Vec<Section> {
                 } else if in_heading {
                     heading_raw.push(' ');
                     heading_sanitized.push(' ');
+                    heading_text.push(' ');
                 } else {
                     buffer.push(' ');
                 }
             }
-            Event::Html(html) if html.trim_start().starts_with("<br") => {
-                if in_heading {
-                    heading_raw.push(' ');
-                    heading_sanitized.push(' ');
-                } else {
-                    buffer.push(' ');
+            Event::Start(Tag::HtmlBlock) => html_block = Some(String::new()),
+            Event::Html(html) if html_block.is_some() => {
+                if let Some(block) = html_block.as_mut() {
+                    block.push_str(&html);
                 }
             }
+            Event::End(TagEnd::HtmlBlock) => {
+                let block = html_block.take().unwrap_or_default();
+                match transparent_html_text(&block) {
+                    Some(text) if !text.is_empty() => {
+                        if let Some(ref mut sec) = current {
+                            sec.lines.push(escape(&text));
+                        }
+                    }
+                    Some(_) => {}
+                    None => report.dropped_html.push(block.trim().to_string()),
+                }
+            }
+            Event::InlineHtml(html) if !is_harmless_html(&html) => {
+                report.dropped_html.push(html.trim().to_string());
+            }
             _ => {}
         }
     }
     if let Some(mut sec) = current {
         if !buffer.trim().is_empty() {
-            let fixed = replace_github_mentions(&fix_bare_link(buffer.trim()));
+            let fixed = replace_github_mentions(&fix_bare_link(buffer.trim(), urls), urls);
             sec.lines.push(fixed);
         }
         sections.push(sec);
     }
-    sections
+    for reference in broken_references {
+        if !report.broken_references.contains(&reference) {
+            report.broken_references.push(reference);
+        }
+    }
+    (sections, report)
 }
@@ -0,0 +1,323 @@
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use teloxide::utils::markdown::escape;
+
+use crate::generator::escape_markdown_url;
+use crate::parser::Section;
+use crate::shared::locale::Messages;
+use crate::shared::urls::UrlOptions;
+
+/// Title of the TWIR subsection with the weekly rustc-perf triage.
+pub const PERF_SECTION: &str = "Rust Compiler Performance Triage";
+
+/// Mean and range of the "All" row of the triage summary table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerfOverall {
+    /// Row label, e.g. `All ❌✅ (primary)`.
+    pub label: String,
+    pub mean: String,
+    pub range: String,
+}
+
+/// Key figures of a compiler performance triage report.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PerfTriage {
+    /// GitHub handle of the person who did the triage.
+    pub triager: Option<String>,
+    /// Link to the triager's GitHub profile.
+    pub triager_url: Option<String>,
+    /// Revision range text, e.g. `956f47c3..4068bafe`.
+    pub revisions: Option<String>,
+    /// Link to the perf.rust-lang.org comparison of the range.
+    pub revisions_url: Option<String>,
+    pub regressions: Option<u32>,
+    pub improvements: Option<u32>,
+    pub mixed: Option<u32>,
+    /// How many of the changes landed in rollups.
+    pub rollups: Option<u32>,
+    /// Number of artifact comparisons made.
+    pub comparisons: Option<u32>,
+    pub overall: Option<PerfOverall>,
+    /// Link to the full triage report.
+    pub report_url: Option<String>,
+}
+
+impl PerfTriage {
+    /// Rewrite the comparison, triager and report links with `urls`, like
+    /// the links of the parsed sections.
+    pub(crate) fn rewrite_urls(&mut self, urls: &UrlOptions) {
+        for url in [
+            &mut self.revisions_url,
+            &mut self.triager_url,
+            &mut self.report_url,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *url = urls.rewrite(url);
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Read "2 Regressions, 5 Improvements, 10 Mixed; 5 of them in rollups"
+    /// and "39 artifact comparisons made in total".
+    fn read_counts(&mut self, text: &str) {
+        for part in text.split([',', ';', '\n']) {
+            let part = part.trim().to_lowercase();
+            let Some((number, rest)) = part.split_once(' ') else {
+                continue;
+            };
+            let Ok(number) = number.parse::<u32>() else {
+                continue;
+            };
+            if rest.starts_with("regression") {
+                self.regressions = Some(number);
+            } else if rest.starts_with("improvement") {
+                self.improvements = Some(number);
+            } else if rest.starts_with("mixed") {
+                self.mixed = Some(number);
+            } else if rest.contains("rollup") {
+                self.rollups = Some(number);
+            } else if rest.starts_with("artifact comparison") {
+                self.comparisons = Some(number);
+            }
+        }
+    }
+
+    fn read_triager(&mut self, text: &str) {
+        if let Some(idx) = text.find("Triage done by") {
+            let name = text[idx + "Triage done by".len()..]
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .trim_matches(|c: char| c == '@' || c == '*' || c == '.');
+            if !name.is_empty() {
+                self.triager = Some(name.to_string());
+                self.triager_url = Some(format!("https://github.com/{name}"));
+            }
+        }
+    }
+
+    fn read_link(&mut self, text: &str, url: &str) {
+        if text.to_lowercase().contains("full report") {
+            self.report_url = Some(url.to_string());
+        } else if text.contains("..") && url.contains("perf.rust-lang.org") {
+            self.revisions = Some(text.to_string());
+            self.revisions_url = Some(url.to_string());
+        }
+    }
+
+    fn read_row(&mut self, cells: &[String]) {
+        if self.overall.is_none() && cells.len() >= 3 && cells[0].trim_start().starts_with("All") {
+            self.overall = Some(PerfOverall {
+                label: cells[0].split_whitespace().collect::<Vec<_>>().join(" "),
+                mean: cells[1].trim().to_string(),
+                range: cells[2].trim().to_string(),
+            });
+        }
+    }
+
+    /// Render the compact summary card shown instead of the full subsection,
+    /// labelled in the language of `messages`.
+    pub fn render(&self, messages: &Messages) -> Vec<String> {
+        let mut lines = Vec::new();
+        let counts: Vec<String> = [
+            ("📉", self.regressions, messages.regressions),
+            ("📈", self.improvements, messages.improvements),
+            ("🔀", self.mixed, messages.mixed),
+        ]
+        .into_iter()
+        .filter_map(|(icon, count, label)| {
+            count.map(|n| format!("{icon} {}", label.replace("{}", &n.to_string())))
+        })
+        .collect();
+        if !counts.is_empty() {
+            let mut line = counts.join(" · ");
+            if let Some(rollups) = self.rollups {
+                line.push_str(&format!(" ({rollups} {})", messages.in_rollups));
+            }
+            lines.push(escape(&line));
+        }
+        if let Some(overall) = &self.overall {
+            lines.push(escape(&format!(
+                "📊 {} {}, {} {}",
+                messages.mean, overall.mean, messages.range, overall.range
+            )));
+        }
+        let mut footer = Vec::new();
+        if let (Some(text), Some(url)) = (&self.revisions, &self.revisions_url) {
+            footer.push(format!("[{}]({})", escape(text), escape_markdown_url(url)));
+        }
+        if let (Some(triager), Some(url)) = (&self.triager, &self.triager_url) {
+            footer.push(format!(
+                "{} [{}]({})",
+                escape(messages.by),
+                escape(triager),
+                escape_markdown_url(url)
+            ));
+        }
+        if let Some(url) = &self.report_url {
+            footer.push(format!(
+                "[{}]({})",
+                escape(messages.full_report),
+                escape_markdown_url(url)
+            ));
+        }
+        if !footer.is_empty() {
+            lines.push(format!("🔎 {}", footer.join(" · ")));
+        }
+        lines
+    }
+}
+
+/// Extract the triage figures from the "Rust Compiler Performance Triage"
+/// subsection of `markdown`.
+///
+/// # Parameters
+/// - `markdown`: Issue body without the metadata header.
+///
+/// # Returns
+/// The triage figures, or `None` when the subsection is missing.
+pub fn parse_perf_triage(markdown: &str) -> Option<PerfTriage> {
+    let mut triage = PerfTriage::default();
+    let mut found = false;
+    let mut in_section = false;
+    let mut heading: Option<String> = None;
+    let mut text = String::new();
+    let mut link: Option<(String, String)> = None;
+    let mut cell: Option<String> = None;
+    let mut row: Vec<String> = Vec::new();
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_TABLES) {
+        match event {
+            Event::Start(Tag::Heading { .. }) => {
+                heading = Some(String::new());
+            }
+            Event::End(TagEnd::Heading(level)) => {
+                let title = heading.take().unwrap_or_default();
+                if level <= HeadingLevel::H3 {
+                    in_section = title.trim().eq_ignore_ascii_case(PERF_SECTION);
+                    found |= in_section;
+                }
+            }
+            _ if !in_section && heading.is_none() => {}
+            Event::Start(Tag::Paragraph) => text.clear(),
+            Event::End(TagEnd::Paragraph) => {
+                triage.read_triager(&text);
+                triage.read_counts(&text);
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                link = Some((dest_url.to_string(), String::new()));
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((url, link_text)) = link.take() {
+                    triage.read_link(link_text.trim(), &url);
+                }
+            }
+            Event::Start(Tag::TableCell) => cell = Some(String::new()),
+            Event::End(TagEnd::TableCell) => row.extend(cell.take()),
+            Event::End(TagEnd::TableHead) => row.clear(),
+            Event::End(TagEnd::TableRow) => {
+                triage.read_row(&row);
+                row.clear();
+            }
+            Event::Text(t) | Event::Code(t) => {
+                if let Some(title) = heading.as_mut() {
+                    title.push_str(&t);
+                    continue;
+                }
+                if let Some((_, link_text)) = link.as_mut() {
+                    link_text.push_str(&t);
+                }
+                match cell.as_mut() {
+                    Some(cell) => cell.push_str(&t),
+                    None => text.push_str(&t),
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => text.push('\n'),
+            _ => {}
+        }
+    }
+    (found && !triage.is_empty()).then_some(triage)
+}
+
+/// Replace the body of the perf triage subsection in `section` with `card`.
+///
+/// Sections promoted from the subsection are replaced as a whole.
+pub(crate) fn replace_perf_triage(section: &mut Section, card: Vec<String>) {
+    let range = if section.title.eq_ignore_ascii_case(PERF_SECTION) {
+        0..section.lines.len()
+    } else if let Some(index) = section
+        .subheadings
+        .iter()
+        .position(|sub| sub.title.eq_ignore_ascii_case(PERF_SECTION))
+    {
+        section.subsection_range(index)
+    } else {
+        return;
+    };
+    section.replace_lines(range, card);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sections;
+    use crate::shared::locale::Locale;
+
+    const ISSUE: &str = "## Updates from the Rust Project\n\n### Rust Compiler Performance Triage\n\nA busy week.\n\nTriage done by **@rylev**.\nRevision range: [45acf54e..42245d34](https://perf.rust-lang.org/?start=a&end=b)\n\n**Summary**:\n\n| (instructions:u) | mean | range | count |\n|:--:|:--:|:--:|:--:|\n| Regressions ❌ <br /> (primary) | 1.1% | [0.2%, 9.1%] | 123 |\n| All ❌✅ (primary) | 1.0% | [-7.3%, 9.1%] | 125 |\n\n2 Regressions, 4 Improvements, 10 Mixed; 7 of them in rollups\n40 artifact comparisons made in total\n\n[Full report here](https://github.com/rust-lang/rustc-perf/blob/master/triage/2025/2025-06-24.md)\n\n### Approved RFCs\n\n* [Not part of triage](https://example.com/)\n";
+
+    #[test]
+    fn parses_triage_figures() {
+        let triage = parse_perf_triage(ISSUE).unwrap();
+        assert_eq!(triage.triager.as_deref(), Some("rylev"));
+        assert_eq!(triage.revisions.as_deref(), Some("45acf54e..42245d34"));
+        assert_eq!(
+            (triage.regressions, triage.improvements, triage.mixed),
+            (Some(2), Some(4), Some(10))
+        );
+        assert_eq!(triage.rollups, Some(7));
+        assert_eq!(triage.comparisons, Some(40));
+        let overall = triage.overall.as_ref().unwrap();
+        assert_eq!(overall.mean, "1.0%");
+        assert_eq!(overall.range, "[-7.3%, 9.1%]");
+        assert_eq!(
+            triage.report_url.as_deref(),
+            Some("https://github.com/rust-lang/rustc-perf/blob/master/triage/2025/2025-06-24.md")
+        );
+    }
+
+    #[test]
+    fn card_replaces_tables() {
+        let triage = parse_perf_triage(ISSUE).unwrap();
+        let mut section = parse_sections(ISSUE).remove(0);
+        replace_perf_triage(&mut section, triage.render(Locale::En.messages()));
+        assert_eq!(
+            section.lines,
+            vec![
+                "\n**Rust Compiler Performance Triage:** 📊",
+                "📉 2 regressions · 📈 4 improvements · 🔀 10 mixed \\(7 in rollups\\)",
+                "📊 mean 1\\.0%, range \\[\\-7\\.3%, 9\\.1%\\]",
+                "🔎 [45acf54e\\.\\.42245d34](https://perf.rust-lang.org/?start=a&end=b) · by [rylev](https://github.com/rylev) · [full report](https://github.com/rust-lang/rustc-perf/blob/master/triage/2025/2025-06-24.md)",
+                "**Approved RFCs**",
+                "• [Not part of triage](https://example.com/)",
+            ]
+        );
+    }
+
+    #[test]
+    fn card_counts_follow_the_locale() {
+        let triage = parse_perf_triage(ISSUE).unwrap();
+        assert_eq!(
+            triage.render(Locale::Ru.messages())[0],
+            "📉 регрессии: 2 · 📈 улучшения: 4 · 🔀 смешанные: 10 \\(7 в роллапах\\)"
+        );
+    }
+
+    #[test]
+    fn missing_subsection_yields_none() {
+        assert_eq!(parse_perf_triage("## Jobs\n\n2 Regressions\n"), None);
+    }
+}
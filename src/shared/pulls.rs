@@ -0,0 +1,302 @@
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+use serde::Deserialize;
+use teloxide::utils::markdown::escape;
+
+use crate::generator::escape_markdown_url;
+use crate::parser::Section;
+use crate::shared::locale::Messages;
+use crate::shared::urls::UrlOptions;
+
+/// Title of the TWIR section announcing merged pull requests.
+pub const PROJECT_SECTION: &str = "Updates from the Rust Project";
+
+/// Per-target settings for the merged pull request digest.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PullOptions {
+    /// Collapse categories with more pull requests than this.
+    pub max_items: Option<usize>,
+    /// Categories that are always collapsed.
+    pub collapse: Vec<String>,
+}
+
+impl PullOptions {
+    fn collapses(&self, category: &PullCategory) -> bool {
+        self.max_items.is_some_and(|max| category.pulls.len() > max)
+            || self
+                .collapse
+                .iter()
+                .any(|name| name.trim().eq_ignore_ascii_case(&category.name))
+    }
+}
+
+/// A merged pull request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequest {
+    pub title: String,
+    pub url: String,
+}
+
+/// Pull requests listed under one `####` heading, e.g. `Compiler`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullCategory {
+    pub name: String,
+    pub pulls: Vec<PullRequest>,
+}
+
+/// Merged pull request statistics of an issue.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PullRequestStats {
+    /// Total from "N pull requests were merged in the last week".
+    pub merged: Option<u32>,
+    /// GitHub search listing the merged pull requests.
+    pub search_url: Option<String>,
+    pub categories: Vec<PullCategory>,
+}
+
+impl PullRequestStats {
+    /// Rewrite the search and pull request links with `urls`, like the
+    /// links of the parsed sections.
+    pub(crate) fn rewrite_urls(&mut self, urls: &UrlOptions) {
+        if let Some(search_url) = &mut self.search_url {
+            *search_url = urls.rewrite(search_url);
+        }
+        for pull in self
+            .categories
+            .iter_mut()
+            .flat_map(|category| &mut category.pulls)
+        {
+            pull.url = urls.rewrite(&pull.url);
+        }
+    }
+
+    /// Render the compact summary, e.g.
+    /// `369 PRs merged: Compiler 9, Library 5`.
+    ///
+    /// # Returns
+    /// `None` when the issue does not state the merged count.
+    pub fn summary_line(&self, messages: &Messages) -> Option<String> {
+        let merged = self.merged?;
+        let head = messages.prs_merged.replace("{}", &merged.to_string());
+        let mut line = match &self.search_url {
+            Some(url) => format!("[{}]({})", escape(&head), escape_markdown_url(url)),
+            None => escape(&head),
+        };
+        let counts: Vec<String> = self
+            .categories
+            .iter()
+            .map(|category| format!("{} {}", category.name, category.pulls.len()))
+            .collect();
+        if !counts.is_empty() {
+            line.push_str(&escape(&format!(": {}", counts.join(", "))));
+        }
+        Some(line)
+    }
+}
+
+/// Extract the merged count and the per-category pull request lists.
+///
+/// Categories are the `####` headings that precede the first `###` heading
+/// of the "Updates from the Rust Project" section.
+///
+/// # Parameters
+/// - `markdown`: Issue body without the metadata header.
+///
+/// # Returns
+/// The collected statistics; empty when the section is missing.
+pub fn parse_pull_requests(markdown: &str) -> PullRequestStats {
+    let mut stats = PullRequestStats::default();
+    let mut in_section = false;
+    let mut heading: Option<(HeadingLevel, String)> = None;
+    let mut paragraph: Option<(String, Option<String>)> = None;
+    let mut depth = 0usize;
+    let mut link: Option<PullRequest> = None;
+    let mut item: Option<PullRequest> = None;
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                heading = Some((level, String::new()));
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                let Some((level, text)) = heading.take() else {
+                    continue;
+                };
+                match level {
+                    HeadingLevel::H1 | HeadingLevel::H2 => {
+                        in_section = text.trim().eq_ignore_ascii_case(PROJECT_SECTION);
+                    }
+                    HeadingLevel::H3 => in_section = false,
+                    HeadingLevel::H4 if in_section => stats.categories.push(PullCategory {
+                        name: text.trim().to_string(),
+                        pulls: Vec::new(),
+                    }),
+                    _ => {}
+                }
+            }
+            _ if !in_section && heading.is_none() => {}
+            Event::Start(Tag::Paragraph) if depth == 0 => {
+                paragraph = Some((String::new(), None));
+            }
+            Event::End(TagEnd::Paragraph) if depth == 0 => {
+                if let Some((text, url)) = paragraph.take()
+                    && stats.merged.is_none()
+                    && text.contains("pull requests were merged")
+                {
+                    stats.merged = text.split_whitespace().next().and_then(|n| n.parse().ok());
+                    stats.search_url = url;
+                }
+            }
+            Event::Start(Tag::Item) => {
+                depth += 1;
+                if depth == 1 {
+                    item = None;
+                }
+            }
+            Event::End(TagEnd::Item) => {
+                if depth == 1
+                    && let Some(done) = item.take()
+                    && let Some(category) = stats.categories.last_mut()
+                {
+                    category.pulls.push(done);
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                if let Some((_, url)) = paragraph.as_mut() {
+                    url.get_or_insert_with(|| dest_url.to_string());
+                } else if depth == 1 && item.is_none() {
+                    link = Some(PullRequest {
+                        title: String::new(),
+                        url: dest_url.to_string(),
+                    });
+                }
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some(done) = link.take() {
+                    item = Some(done);
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, heading_text)) = heading.as_mut() {
+                    heading_text.push_str(&text);
+                } else if let Some((paragraph_text, _)) = paragraph.as_mut() {
+                    paragraph_text.push_str(&text);
+                } else if let Some(current) = link.as_mut() {
+                    current.title.push_str(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+    stats
+}
+
+/// Replace the merged count sentence with the summary line and collapse long
+/// categories into a count that points to the web version.
+///
+/// Works on the "Updates from the Rust Project" section and on sections
+/// promoted from one of its categories.
+pub(crate) fn apply_pull_digest(
+    section: &mut Section,
+    stats: &PullRequestStats,
+    options: &PullOptions,
+    web_url: Option<&str>,
+    messages: &Messages,
+) {
+    if section.title.eq_ignore_ascii_case(PROJECT_SECTION) {
+        let intro_end = section
+            .subheadings
+            .first()
+            .map_or(section.lines.len(), |sub| sub.line);
+        if let Some(summary) = stats.summary_line(messages)
+            && let Some(index) =
+                (0..intro_end).find(|&i| section.lines[i].contains("pull requests were"))
+        {
+            section.replace_lines(index..index + 1, vec![summary]);
+        }
+    }
+
+    for category in stats.categories.iter().filter(|c| options.collapses(c)) {
+        let count = category.pulls.len();
+        let mut line = escape(&messages.pull_requests.replace("{}", &count.to_string()));
+        if let Some(url) = web_url {
+            line.push_str(&format!(
+                " — [{}]({})",
+                escape(messages.see_web_version),
+                escape_markdown_url(url)
+            ));
+        }
+        if section.title.eq_ignore_ascii_case(&category.name) {
+            let range = 0..section.lines.len();
+            section.replace_lines(range, vec![line]);
+        } else if let Some(index) = section
+            .subheadings
+            .iter()
+            .position(|sub| sub.level == 4 && sub.title.eq_ignore_ascii_case(&category.name))
+        {
+            let range = section.subsection_range(index);
+            section.replace_lines(range, vec![line]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sections;
+    use crate::shared::locale::Locale;
+
+    const ISSUE: &str = "## Updates from the Rust Project\n\n369 pull requests were [merged in the last week][merged]\n\n[merged]: https://github.com/search?q=merged\n\n#### Compiler\n* [a](https://github.com/rust-lang/rust/pull/1)\n* [b](https://github.com/rust-lang/rust/pull/2)\n    * [nested](https://example.com/)\n\n#### Library\n* [c](https://github.com/rust-lang/rust/pull/3)\n\n### Rust Compiler Performance Triage\n\n#### Regressions\n* [not a pull](https://example.com/)\n";
+
+    #[test]
+    fn parses_merged_count_and_categories() {
+        let stats = parse_pull_requests(ISSUE);
+        assert_eq!(stats.merged, Some(369));
+        assert_eq!(
+            stats.search_url.as_deref(),
+            Some("https://github.com/search?q=merged")
+        );
+        let names: Vec<&str> = stats.categories.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Compiler", "Library"]);
+        assert_eq!(stats.categories[0].pulls.len(), 2);
+        assert_eq!(stats.categories[0].pulls[1].title, "b");
+    }
+
+    #[test]
+    fn summary_lists_category_counts() {
+        assert_eq!(
+            parse_pull_requests(ISSUE)
+                .summary_line(Locale::En.messages())
+                .unwrap(),
+            "[369 PRs merged](https://github.com/search?q=merged): Compiler 2, Library 1"
+        );
+    }
+
+    #[test]
+    fn long_categories_are_collapsed() {
+        let stats = parse_pull_requests(ISSUE);
+        let options = PullOptions {
+            max_items: Some(1),
+            collapse: Vec::new(),
+        };
+        let mut section = parse_sections(ISSUE).remove(0);
+        apply_pull_digest(
+            &mut section,
+            &stats,
+            &options,
+            Some("https://web.example/"),
+            Locale::En.messages(),
+        );
+        assert_eq!(
+            section.lines[..4],
+            [
+                "[369 PRs merged](https://github.com/search?q=merged): Compiler 2, Library 1",
+                "\n**Compiler:** 🛠️",
+                "2 pull requests — [see web version](https://web.example/)",
+                "\n**Library:** 📚",
+            ]
+        );
+        assert_eq!(section.subheadings[1].line, 3);
+    }
+}
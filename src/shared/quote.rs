@@ -0,0 +1,274 @@
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use serde::Deserialize;
+use std::io;
+use teloxide::utils::markdown::escape;
+
+use crate::generator::escape_markdown_url;
+use crate::parser::Section;
+use crate::shared::card::{Canvas, CardOptions, CardStyle, text_width, wrap_text};
+
+/// Title of the TWIR quote heading.
+pub const QUOTE_SECTION: &str = "Quote of the Week";
+
+/// File name of the rendered quote card.
+pub const QUOTE_CARD_FILE: &str = "quote.png";
+
+/// Settings of the Quote of the Week.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuoteOptions {
+    /// Publish the quote as a post of its own after the issue posts.
+    pub standalone: bool,
+    /// Render the quote as an image instead; it replaces the quote post.
+    pub card: Option<CardOptions>,
+}
+
+impl QuoteOptions {
+    /// Whether the quote is taken out of the issue posts.
+    pub fn detached(&self) -> bool {
+        self.standalone || self.card.is_some()
+    }
+}
+
+/// The Quote of the Week as plain text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quote {
+    pub paragraphs: Vec<String>,
+    /// Who said it, without the leading dash.
+    pub attribution: Option<String>,
+    /// Link of the attribution, usually the source post.
+    pub url: Option<String>,
+}
+
+/// Extract the Quote of the Week from `markdown`.
+///
+/// The quote is the block quote following the heading; a paragraph starting
+/// with a dash after it is the attribution.
+///
+/// # Parameters
+/// - `markdown`: Issue body without the metadata header.
+///
+/// # Returns
+/// `None` when the issue has no quote.
+pub fn parse_quote(markdown: &str) -> Option<Quote> {
+    let mut heading: Option<String> = None;
+    let mut in_quote_section = false;
+    let mut depth = 0usize;
+    let mut paragraph: Option<String> = None;
+    let mut quote = Quote {
+        paragraphs: Vec::new(),
+        attribution: None,
+        url: None,
+    };
+    let mut url = None;
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Heading { .. }) => heading = Some(String::new()),
+            Event::End(TagEnd::Heading(_)) => {
+                let text = heading.take().unwrap_or_default();
+                if in_quote_section {
+                    break;
+                }
+                in_quote_section = text.trim().eq_ignore_ascii_case(QUOTE_SECTION);
+            }
+            Event::Text(text) | Event::Code(text) if heading.is_some() => {
+                if let Some(heading) = heading.as_mut() {
+                    heading.push_str(&text);
+                }
+            }
+            _ if !in_quote_section => {}
+            Event::Start(Tag::BlockQuote(_)) => depth += 1,
+            Event::End(TagEnd::BlockQuote(_)) => depth = depth.saturating_sub(1),
+            Event::Start(Tag::Paragraph) => paragraph = Some(String::new()),
+            Event::End(TagEnd::Paragraph) => {
+                let text = paragraph.take().unwrap_or_default();
+                let text = text.trim();
+                if depth > 0 {
+                    if !text.is_empty() {
+                        quote.paragraphs.push(text.to_string());
+                    }
+                } else if !quote.paragraphs.is_empty()
+                    && let Some(rest) = text.strip_prefix(['–', '—', '-'])
+                {
+                    quote.attribution = Some(rest.trim().to_string());
+                    quote.url = url.take();
+                    break;
+                }
+                url = None;
+            }
+            Event::Start(Tag::Link { dest_url, .. }) if depth == 0 && url.is_none() => {
+                url = Some(dest_url.to_string());
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(paragraph) = paragraph.as_mut() {
+                    paragraph.push_str(&text);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some(paragraph) = paragraph.as_mut() {
+                    paragraph.push(' ');
+                }
+            }
+            _ => {}
+        }
+    }
+    (!quote.paragraphs.is_empty()).then_some(quote)
+}
+
+impl Quote {
+    /// Render the attribution line, e.g. `— [Remo Senekowitsch](https://…)`.
+    pub(crate) fn attribution_line(&self) -> Option<String> {
+        let attribution = self.attribution.as_deref()?;
+        Some(match &self.url {
+            Some(url) => format!("— [{}]({})", escape(attribution), escape_markdown_url(url)),
+            None => format!("— {}", escape(attribution)),
+        })
+    }
+
+    /// Build the standalone quote post as a section whose paragraphs form a
+    /// Telegram block quote.
+    pub fn to_section(&self) -> Section {
+        let mut lines = Vec::new();
+        for (index, paragraph) in self.paragraphs.iter().enumerate() {
+            if index > 0 {
+                lines.push(">".to_string());
+            }
+            lines.push(format!(">{}", escape(paragraph)));
+        }
+        lines.extend(self.attribution_line());
+        Section {
+            title: QUOTE_SECTION.to_string(),
+            lines,
+            ..Section::default()
+        }
+    }
+}
+
+/// Render `quote` as a PNG image.
+///
+/// # Parameters
+/// - `quote`: Quote to draw.
+/// - `title`: Label drawn above the quote, e.g. the localized section title.
+/// - `options`: Font, width and colors of the card.
+///
+/// # Returns
+/// The encoded PNG.
+///
+/// # Errors
+/// Returns an error if the font cannot be read or parsed, a color is not
+/// `#rrggbb` or the width is below 320 pixels.
+pub fn render_quote_card(quote: &Quote, title: &str, options: &CardOptions) -> io::Result<Vec<u8>> {
+    let CardStyle {
+        font,
+        background,
+        foreground,
+        accent,
+    } = options.style()?;
+
+    let width = options.width as f32;
+    let padding = width / 12.0;
+    let size = width / 24.0;
+    let small = size * 0.75;
+    let line_height = size * 1.4;
+    let text_left = padding * 1.4;
+    let text_width_max = width - text_left - padding;
+
+    let paragraphs: Vec<Vec<String>> = quote
+        .paragraphs
+        .iter()
+        .map(|paragraph| {
+            wrap_text(paragraph, text_width_max, |line| {
+                text_width(&font, size, line)
+            })
+        })
+        .collect();
+    let attribution = quote
+        .attribution
+        .as_deref()
+        .map(|text| {
+            wrap_text(&format!("— {text}"), text_width_max, |line| {
+                text_width(&font, small, line)
+            })
+        })
+        .unwrap_or_default();
+
+    let title_height = small * 2.0;
+    let quote_lines: usize = paragraphs.iter().map(Vec::len).sum();
+    let gaps = paragraphs.len().saturating_sub(1) as f32 * line_height * 0.5;
+    let quote_height = quote_lines as f32 * line_height + gaps;
+    let attribution_height = if attribution.is_empty() {
+        0.0
+    } else {
+        line_height * 0.5 + attribution.len() as f32 * small * 1.4
+    };
+    let height = (padding * 2.0 + title_height + quote_height + attribution_height).ceil() as u32;
+
+    let mut canvas = Canvas::new(options.width, height, background);
+    let mut y = padding + small;
+    canvas.text(&font, small, padding, y, title, accent);
+    y += title_height - small;
+    let bar = (width / 200.0).max(4.0);
+    canvas.fill(
+        padding as u32,
+        y as u32,
+        bar as u32,
+        quote_height.ceil() as u32,
+        accent,
+    );
+    for (index, lines) in paragraphs.iter().enumerate() {
+        if index > 0 {
+            y += line_height * 0.5;
+        }
+        for line in lines {
+            canvas.text(&font, size, text_left, y + size, line, foreground);
+            y += line_height;
+        }
+    }
+    if !attribution.is_empty() {
+        y += line_height * 0.5;
+        for line in &attribution {
+            canvas.text(&font, small, text_left, y + small, line, foreground);
+            y += small * 1.4;
+        }
+    }
+    canvas.encode()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUE: &str = "## Jobs\n\nSee the thread.\n\n# Quote of the Week\n\n> First line\n> continues here.\n>\n> Second `paragraph`.\n\n– [Jane Doe on rust-users](https://users.rust-lang.org/t/1)\n\nThanks to [llogiq](https://example.com) for the suggestion!\n";
+
+    #[test]
+    fn parses_quote_and_attribution() {
+        let quote = parse_quote(ISSUE).unwrap();
+        assert_eq!(
+            quote,
+            Quote {
+                paragraphs: vec![
+                    "First line continues here.".into(),
+                    "Second paragraph.".into()
+                ],
+                attribution: Some("Jane Doe on rust-users".into()),
+                url: Some("https://users.rust-lang.org/t/1".into()),
+            }
+        );
+        assert!(parse_quote("## Jobs\n\n> Not a quote of the week\n").is_none());
+    }
+
+    #[test]
+    fn section_uses_block_quote_lines() {
+        let section = parse_quote(ISSUE).unwrap().to_section();
+        assert_eq!(
+            section.lines,
+            vec![
+                ">First line continues here\\.",
+                ">",
+                ">Second paragraph\\.",
+                "— [Jane Doe on rust\\-users](https://users.rust-lang.org/t/1)",
+            ]
+        );
+    }
+}
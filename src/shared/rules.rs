@@ -0,0 +1,318 @@
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use std::{fs, io, path::Path};
+
+use crate::parser::Section;
+
+/// What a [`Rule`] does with a matching line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleAction {
+    /// Remove the line.
+    Drop,
+    /// Replace every match of the line pattern; `$1` and `${name}` refer to
+    /// capture groups.
+    Replace(String),
+    /// Surround the line text, after any bullet, with a marker such as `_`.
+    Wrap(String),
+    /// Move the line to the end of the section with this title, creating the
+    /// section when the issue has none.
+    Move(String),
+}
+
+/// A declarative line rewrite applied to the rendered sections.
+///
+/// Rules are read from `[[rules]]` tables of the generation options or a
+/// rules file, for example:
+///
+/// ```toml
+/// [[rules]]
+/// section = "Call for Participation.*"
+/// line = "^Always wanted to contribute"
+/// action = "drop"
+/// ```
+///
+/// Lines are matched as rendered Telegram Markdown, so a literal `.` in the
+/// issue appears as `\.` in the line.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "RawRule")]
+pub struct Rule {
+    /// Sections the rule applies to; every section when `None`.
+    pub section: Option<Regex>,
+    pub line: Regex,
+    pub action: RuleAction,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RawAction {
+    Drop,
+    Replace,
+    Wrap,
+    Move,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRule {
+    section: Option<String>,
+    line: String,
+    action: RawAction,
+    with: Option<String>,
+    to: Option<String>,
+}
+
+impl TryFrom<RawRule> for Rule {
+    type Error = String;
+
+    fn try_from(raw: RawRule) -> Result<Self, Self::Error> {
+        let section = raw
+            .section
+            .map(|title| {
+                RegexBuilder::new(&format!("^(?:{title})$"))
+                    .case_insensitive(true)
+                    .build()
+            })
+            .transpose()
+            .map_err(|e| e.to_string())?;
+        let line = Regex::new(&raw.line).map_err(|e| e.to_string())?;
+        let action = match (raw.action, raw.with, raw.to) {
+            (RawAction::Drop, None, None) => RuleAction::Drop,
+            (RawAction::Replace, Some(with), None) => RuleAction::Replace(with),
+            (RawAction::Wrap, Some(with), None) if !with.is_empty() => RuleAction::Wrap(with),
+            (RawAction::Move, None, Some(to)) if !to.trim().is_empty() => RuleAction::Move(to),
+            (RawAction::Drop, ..) => return Err("`drop` takes no `with` or `to`".into()),
+            (RawAction::Replace, ..) => return Err("`replace` needs `with` and no `to`".into()),
+            (RawAction::Wrap, ..) => return Err("`wrap` needs a non-empty `with`".into()),
+            (RawAction::Move, ..) => return Err("`move` needs a non-empty `to`".into()),
+        };
+        Ok(Rule {
+            section,
+            line,
+            action,
+        })
+    }
+}
+
+impl Rule {
+    fn selects(&self, section: &Section) -> bool {
+        self.section
+            .as_ref()
+            .is_none_or(|title| title.is_match(section.title.trim()))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+/// Read the `[[rules]]` tables of the TOML file at `path`.
+///
+/// # Errors
+/// Returns an error if the file cannot be read, does not match the expected
+/// schema or holds an invalid regular expression.
+pub fn load_rules(path: &Path) -> io::Result<Vec<Rule>> {
+    let text = fs::read_to_string(path)?;
+    toml::from_str::<RulesFile>(&text)
+        .map(|file| file.rules)
+        .map_err(|e| io::Error::other(format!("invalid rules {}: {e}", path.display())))
+}
+
+/// Split `line` into its indentation and bullet, and the text after them.
+fn split_bullet(line: &str) -> (&str, &str) {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+    let bullet = rest.strip_prefix("• ").map_or(0, |_| "• ".len());
+    line.split_at(indent + bullet)
+}
+
+/// Apply `rules` in order to `sections`.
+///
+/// Each rule sees the lines left by the previous ones. Sections emptied by
+/// the rules are removed.
+pub(crate) fn apply_rules(sections: &mut Vec<Section>, rules: &[Rule]) {
+    if rules.is_empty() {
+        return;
+    }
+    let mut touched = vec![false; sections.len()];
+    for rule in rules {
+        let mut moved = Vec::new();
+        for (section, touched) in sections.iter_mut().zip(touched.iter_mut()) {
+            if !rule.selects(section) {
+                continue;
+            }
+            let mut index = 0;
+            while index < section.lines.len() {
+                let line = &section.lines[index];
+                if !rule.line.is_match(line) {
+                    index += 1;
+                    continue;
+                }
+                *touched = true;
+                match &rule.action {
+                    RuleAction::Drop => section.replace_lines(index..index + 1, Vec::new()),
+                    RuleAction::Move(_) => {
+                        moved.push(line.clone());
+                        section.replace_lines(index..index + 1, Vec::new());
+                    }
+                    RuleAction::Replace(with) => {
+                        section.lines[index] = rule.line.replace_all(line, with.as_str()).into();
+                        index += 1;
+                    }
+                    RuleAction::Wrap(marker) => {
+                        let (prefix, text) = split_bullet(line);
+                        if !text.trim().is_empty() {
+                            section.lines[index] = format!("{prefix}{marker}{text}{marker}");
+                        }
+                        index += 1;
+                    }
+                }
+            }
+        }
+        if let RuleAction::Move(to) = &rule.action
+            && !moved.is_empty()
+        {
+            let target = sections
+                .iter()
+                .position(|section| section.title.trim().eq_ignore_ascii_case(to.trim()));
+            let target = target.unwrap_or_else(|| {
+                sections.push(Section {
+                    title: to.trim().to_string(),
+                    ..Section::default()
+                });
+                touched.push(false);
+                sections.len() - 1
+            });
+            sections[target].lines.extend(moved);
+        }
+    }
+    let mut touched = touched.into_iter();
+    sections.retain(|section| {
+        !touched.next().unwrap_or(false) || section.lines.iter().any(|line| !line.trim().is_empty())
+    });
+}
+
+/// Lines of a section before and after the rules ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleChange {
+    pub title: String,
+    /// Empty when a `move` rule created the section.
+    pub before: Vec<String>,
+    /// Empty when the rules removed the section.
+    pub after: Vec<String>,
+}
+
+/// Pair the sections of `before` and `after` by title and keep the changed
+/// ones.
+pub(crate) fn rule_changes(before: &[Section], after: &[Section]) -> Vec<RuleChange> {
+    let find = |sections: &[Section], title: &str| {
+        sections
+            .iter()
+            .find(|section| section.title == title)
+            .map(|section| section.lines.clone())
+            .unwrap_or_default()
+    };
+    let mut titles: Vec<&str> = before
+        .iter()
+        .map(|section| section.title.as_str())
+        .collect();
+    for section in after {
+        if !titles.contains(&section.title.as_str()) {
+            titles.push(&section.title);
+        }
+    }
+    titles
+        .into_iter()
+        .map(|title| RuleChange {
+            title: title.to_string(),
+            before: find(before, title),
+            after: find(after, title),
+        })
+        .filter(|change| change.before != change.after)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(text: &str) -> Vec<Rule> {
+        toml::from_str::<RulesFile>(text).unwrap().rules
+    }
+
+    fn section(title: &str, lines: &[&str]) -> Section {
+        Section {
+            title: title.to_string(),
+            lines: lines.iter().map(|line| line.to_string()).collect(),
+            ..Section::default()
+        }
+    }
+
+    #[test]
+    fn drops_replaces_and_wraps_lines() {
+        let rules = rules(
+            r#"
+[[rules]]
+section = "call for participation.*"
+line = "^Always wanted"
+action = "drop"
+
+[[rules]]
+line = "r/rust"
+action = "replace"
+with = "Reddit"
+
+[[rules]]
+section = "Jobs"
+line = "^• "
+action = "wrap"
+with = "_"
+"#,
+        );
+        let mut sections = vec![
+            section(
+                "Call for Participation; projects and speakers",
+                &["Always wanted to contribute?", "• Task on r/rust"],
+            ),
+            section("Jobs", &["Always wanted a job?", "• Thread on r/rust"]),
+        ];
+        apply_rules(&mut sections, &rules);
+        assert_eq!(sections[0].lines, vec!["• Task on Reddit"]);
+        assert_eq!(
+            sections[1].lines,
+            vec!["Always wanted a job?", "• _Thread on Reddit_"]
+        );
+    }
+
+    #[test]
+    fn moves_lines_and_removes_emptied_sections() {
+        let rules = rules(
+            r#"
+[[rules]]
+section = "Miscellaneous"
+line = "video"
+action = "move"
+to = "Videos"
+"#,
+        );
+        let mut sections = vec![
+            section("Miscellaneous", &["• A video", ""]),
+            section("Jobs", &[]),
+        ];
+        apply_rules(&mut sections, &rules);
+        let titles: Vec<_> = sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Jobs", "Videos"]);
+        assert_eq!(sections[1].lines, vec!["• A video"]);
+    }
+
+    #[test]
+    fn rejects_incomplete_rules() {
+        let parse = |text: &str| toml::from_str::<RulesFile>(text);
+        assert!(parse("[[rules]]\nline = \"x\"\naction = \"replace\"").is_err());
+        assert!(parse("[[rules]]\nline = \"x\"\naction = \"move\"").is_err());
+        assert!(parse("[[rules]]\nline = \"(\"\naction = \"drop\"").is_err());
+        assert!(parse("[[rules]]\nline = \"x\"\naction = \"drop\"\nto = \"Jobs\"").is_err());
+    }
+}
@@ -0,0 +1,350 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use teloxide::utils::markdown::escape;
+
+use crate::parser::Section;
+use crate::shared::events::EVENTS_SECTION;
+use crate::shared::locale::Messages;
+
+/// Selection, ordering and truncation rules applied to parsed sections.
+///
+/// Titles match both `##` sections and the subheadings inside them,
+/// case-insensitively. A subsection listed in `include` whose parent section
+/// is not included is promoted to a section of its own.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SectionPolicy {
+    /// Keep only these sections or subsections. Empty keeps everything.
+    pub include: Vec<String>,
+    /// Drop these sections or subsections. "Upcoming Events" is always
+    /// dropped unless it is listed in `include`.
+    pub exclude: Vec<String>,
+    /// Sections to move to the front, in this order.
+    pub order: Vec<String>,
+    /// Alternative names mapped to the title used in the issue.
+    pub aliases: HashMap<String, String>,
+    /// Limits on the number of list items shown per section or subsection.
+    pub truncate: Vec<TruncateRule>,
+}
+
+/// Sections dropped unless listed in `include`, on top of `exclude`.
+///
+/// The event listing is published through the event digest instead.
+const BUILTIN_EXCLUDE: &[&str] = &[EVENTS_SECTION];
+
+/// Show only the first `max_items` list items of a section or subsection.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TruncateRule {
+    pub title: String,
+    pub max_items: usize,
+}
+
+fn normalize_title(title: &str) -> String {
+    title
+        .trim()
+        .trim_end_matches(':')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+impl SectionPolicy {
+    fn canonical(&self, title: &str) -> String {
+        let normalized = normalize_title(title);
+        self.aliases
+            .iter()
+            .find(|(alias, _)| normalize_title(alias) == normalized)
+            .map_or(normalized, |(_, target)| normalize_title(target))
+    }
+
+    fn canonical_list(&self, titles: &[String]) -> Vec<String> {
+        titles.iter().map(|title| self.canonical(title)).collect()
+    }
+
+    /// Whether `include` and `exclude` let a section titled `title` through.
+    ///
+    /// The built-in exclusion of "Upcoming Events" is not considered, so
+    /// content that replaces that section can be checked against the
+    /// user's lists alone.
+    pub(crate) fn allows(&self, title: &str) -> bool {
+        let title = self.canonical(title);
+        !self.canonical_list(&self.exclude).contains(&title)
+            && (self.include.is_empty() || self.canonical_list(&self.include).contains(&title))
+    }
+}
+
+/// Apply `policy` to sections freshly returned by
+/// [`parse_sections`](crate::parser::parse_sections).
+///
+/// Subheading line indices must still be accurate, so this runs before any
+/// transform rewrites section lines.
+pub(crate) fn apply_section_policy(
+    sections: Vec<Section>,
+    policy: &SectionPolicy,
+    messages: &Messages,
+) -> Vec<Section> {
+    let include = policy.canonical_list(&policy.include);
+    let mut exclude = policy.canonical_list(&policy.exclude);
+    exclude.extend(
+        BUILTIN_EXCLUDE
+            .iter()
+            .map(|title| policy.canonical(title))
+            .filter(|title| !include.contains(title)),
+    );
+    let mut selected = Vec::new();
+
+    for mut section in sections {
+        let title = policy.canonical(&section.title);
+        if exclude.contains(&title) {
+            continue;
+        }
+        for rule in &policy.truncate {
+            truncate_matching(
+                &mut section,
+                &policy.canonical(&rule.title),
+                rule.max_items,
+                policy,
+                messages,
+            );
+        }
+        drop_subsections(&mut section, &exclude, policy);
+
+        if include.is_empty() || include.contains(&title) {
+            selected.push(section);
+            continue;
+        }
+        for index in 0..section.subheadings.len() {
+            if include.contains(&policy.canonical(&section.subheadings[index].title)) {
+                selected.push(promote_subsection(&section, index));
+            }
+        }
+    }
+
+    let order = policy.canonical_list(&policy.order);
+    selected.sort_by_key(|section| {
+        let title = policy.canonical(&section.title);
+        order
+            .iter()
+            .position(|entry| *entry == title)
+            .unwrap_or(order.len())
+    });
+    selected
+}
+
+/// Build a standalone section from the subheading at `index`.
+fn promote_subsection(section: &Section, index: usize) -> Section {
+    let range = section.subsection_range(index);
+    let offset = range.start;
+    let subheadings = section.subheadings[index + 1..]
+        .iter()
+        .filter(|sub| range.contains(&sub.line))
+        .map(|sub| crate::parser::Subheading {
+            line: sub.line - offset,
+            ..sub.clone()
+        })
+        .collect();
+    Section {
+        title: section.subheadings[index].title.clone(),
+        lines: section.lines[range].to_vec(),
+        subheadings,
+    }
+}
+
+/// Remove excluded subsections, heading included, from `section`.
+fn drop_subsections(section: &mut Section, exclude: &[String], policy: &SectionPolicy) {
+    while let Some(index) = section
+        .subheadings
+        .iter()
+        .position(|sub| exclude.contains(&policy.canonical(&sub.title)))
+    {
+        let range = section.subsection_range(index);
+        let start = section.subheadings[index].line;
+        remove_lines(section, start..range.end);
+    }
+}
+
+fn truncate_matching(
+    section: &mut Section,
+    title: &str,
+    max_items: usize,
+    policy: &SectionPolicy,
+    messages: &Messages,
+) {
+    if policy.canonical(&section.title) == title {
+        let range = 0..section.lines.len();
+        truncate_items(section, range, max_items, messages);
+        return;
+    }
+    if let Some(index) = section
+        .subheadings
+        .iter()
+        .position(|sub| policy.canonical(&sub.title) == title)
+    {
+        let range = section.subsection_range(index);
+        truncate_items(section, range, max_items, messages);
+    }
+}
+
+/// Keep the first `max_items` top-level list items in `range` and replace the
+/// rest with an "and N more" line.
+fn truncate_items(
+    section: &mut Section,
+    range: std::ops::Range<usize>,
+    max_items: usize,
+    messages: &Messages,
+) {
+    let mut items = 0usize;
+    let mut dropping = false;
+    let mut dropped = Vec::new();
+    for idx in range {
+        let line = &section.lines[idx];
+        if line.starts_with('•') {
+            items += 1;
+            dropping = items > max_items;
+        } else if !line.starts_with(' ') {
+            dropping = false;
+        }
+        if dropping {
+            dropped.push(idx);
+        }
+    }
+    let Some(&first) = dropped.first() else {
+        return;
+    };
+    let hidden = items - max_items;
+    for idx in dropped.into_iter().rev() {
+        remove_lines(section, idx..idx + 1);
+    }
+    let more = messages.more_items.replace("{}", &hidden.to_string());
+    insert_line(section, first, format!("_{}_", escape(&more)));
+}
+
+fn remove_lines(section: &mut Section, range: std::ops::Range<usize>) {
+    section.replace_lines(range, Vec::new());
+}
+
+fn insert_line(section: &mut Section, index: usize, line: String) {
+    section.replace_lines(index..index, vec![line]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sections;
+    use crate::shared::locale::Locale;
+
+    const ISSUE: &str = "## Updates\n### Official\n* [A](https://a.example)\n### Newsletters\n* [B](https://b.example)\n## Crate of the Week\nThis week's crate is great\\.\n## Upcoming Events\n* event\n## Jobs\nHiring\n# Quote of the Week\n> quote\n";
+
+    fn titles(sections: &[Section]) -> Vec<&str> {
+        sections.iter().map(|s| s.title.as_str()).collect()
+    }
+
+    #[test]
+    fn default_policy_drops_upcoming_events() {
+        let sections = apply_section_policy(
+            parse_sections(ISSUE),
+            &SectionPolicy::default(),
+            Locale::En.messages(),
+        );
+        assert_eq!(
+            titles(&sections),
+            vec!["Updates", "Crate of the Week", "Jobs"]
+        );
+    }
+
+    #[test]
+    fn custom_exclude_keeps_dropping_upcoming_events() {
+        let policy = SectionPolicy {
+            exclude: vec!["Jobs".into()],
+            ..SectionPolicy::default()
+        };
+        let sections = apply_section_policy(parse_sections(ISSUE), &policy, Locale::En.messages());
+        assert_eq!(titles(&sections), vec!["Updates", "Crate of the Week"]);
+
+        let policy = SectionPolicy {
+            include: vec!["upcoming events".into()],
+            ..SectionPolicy::default()
+        };
+        let sections = apply_section_policy(parse_sections(ISSUE), &policy, Locale::En.messages());
+        assert_eq!(titles(&sections), vec!["Upcoming Events"]);
+    }
+
+    #[test]
+    fn allows_checks_user_lists_only() {
+        assert!(SectionPolicy::default().allows("Upcoming Events"));
+        let policy = SectionPolicy {
+            include: vec!["Jobs".into()],
+            ..SectionPolicy::default()
+        };
+        assert!(policy.allows("jobs"));
+        assert!(!policy.allows("Upcoming Events"));
+        let policy = SectionPolicy {
+            exclude: vec!["upcoming events".into()],
+            ..SectionPolicy::default()
+        };
+        assert!(!policy.allows("Upcoming Events"));
+    }
+
+    #[test]
+    fn include_promotes_subsections_and_orders() {
+        let policy = SectionPolicy {
+            include: vec![
+                "official updates".into(),
+                "CRATE OF THE WEEK".into(),
+                "Quote of the Week".into(),
+            ],
+            order: vec!["crate of the week".into()],
+            aliases: HashMap::from([("Official updates".into(), "Official".into())]),
+            ..SectionPolicy::default()
+        };
+        let sections = apply_section_policy(parse_sections(ISSUE), &policy, Locale::En.messages());
+        assert_eq!(
+            titles(&sections),
+            vec!["Crate of the Week", "Official", "Quote of the Week"]
+        );
+        assert_eq!(sections[1].lines, vec!["• [A](https://a.example)"]);
+        assert_eq!(sections[2].lines, vec!["\\> quote"]);
+    }
+
+    #[test]
+    fn exclude_drops_subsections() {
+        let policy = SectionPolicy {
+            exclude: vec!["newsletters".into()],
+            ..SectionPolicy::default()
+        };
+        let sections = apply_section_policy(parse_sections(ISSUE), &policy, Locale::En.messages());
+        assert_eq!(
+            sections[0].lines,
+            vec!["**Official**", "• [A](https://a.example)"]
+        );
+    }
+
+    #[test]
+    fn truncate_keeps_first_items() {
+        let input =
+            "## News\n### Tools\n* one\n  * nested\n* two\n* three\n* four\n### Other\n* five\n";
+        let policy = SectionPolicy {
+            truncate: vec![TruncateRule {
+                title: "tools".into(),
+                max_items: 2,
+            }],
+            ..SectionPolicy::default()
+        };
+        let sections = apply_section_policy(parse_sections(input), &policy, Locale::En.messages());
+        assert_eq!(
+            sections[0].lines,
+            vec![
+                "**Tools**",
+                "• one",
+                "  • nested",
+                "• two",
+                "_…and 2 more_",
+                "**Other**",
+                "• five"
+            ]
+        );
+        assert_eq!(sections[0].subheadings[1].line, 5);
+    }
+}
@@ -0,0 +1,182 @@
+use phf::phf_map;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::parser::Section;
+
+/// Emoji shown in place of TWIR's bracketed media and language tags.
+pub static MEDIA_TAG_EMOJIS: phf::Map<&'static str, &'static str> = phf_map! {
+    "audio" => "🎧",
+    "podcast" => "🎙️",
+    "video" => "🎬",
+    "series" => "📚",
+    "slides" => "🖼️",
+    "de" => "🇩🇪",
+    "es" => "🇪🇸",
+    "fr" => "🇫🇷",
+    "it" => "🇮🇹",
+    "ja" => "🇯🇵",
+    "jp" => "🇯🇵",
+    "ko" => "🇰🇷",
+    "pl" => "🇵🇱",
+    "pt" => "🇵🇹",
+    "ru" => "🇷🇺",
+    "tr" => "🇹🇷",
+    "ua" => "🇺🇦",
+    "zh" => "🇨🇳",
+};
+
+/// Settings for media tags such as `[audio]` or `[ZH]`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TagOptions {
+    /// Extra or replacement icons keyed by tag.
+    pub icons: BTreeMap<String, String>,
+    /// Drop list items carrying any of these tags.
+    pub exclude: Vec<String>,
+}
+
+impl TagOptions {
+    fn icon(&self, tag: &str) -> Option<&str> {
+        self.icons
+            .iter()
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case(tag))
+            .map(|(_, icon)| icon.as_str())
+            .or_else(|| MEDIA_TAG_EMOJIS.get(tag).copied())
+    }
+
+    fn excludes(&self, tags: &[MediaTag<'_>]) -> bool {
+        tags.iter().any(|tag| {
+            self.exclude
+                .iter()
+                .any(|name| name.trim().eq_ignore_ascii_case(&tag.name))
+        })
+    }
+}
+
+/// A tag found at the start of a rendered list item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaTag<'a> {
+    /// Lowercase tag name, e.g. `zh`.
+    pub name: String,
+    /// Escaped source text, e.g. `\[ZH\]`.
+    pub raw: &'a str,
+}
+
+/// Split the leading `\[tag\]` markers off the text of a rendered item.
+///
+/// # Returns
+/// The tags and the remaining text.
+pub fn split_media_tags(text: &str) -> (Vec<MediaTag<'_>>, &str) {
+    let mut tags = Vec::new();
+    let mut rest = text;
+    while let Some(inner) = rest.strip_prefix("\\[")
+        && let Some(end) = inner.find("\\]")
+        && (1..=12).contains(&end)
+        && inner[..end]
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-')
+    {
+        let raw_len = end + 4;
+        tags.push(MediaTag {
+            name: inner[..end].to_lowercase(),
+            raw: &rest[..raw_len],
+        });
+        rest = rest[raw_len..].trim_start();
+    }
+    (tags, rest)
+}
+
+/// Replace known media tags with icons and drop items with excluded tags.
+///
+/// Unknown tags stay as written. Nested items of a dropped item are dropped
+/// with it.
+pub(crate) fn apply_media_tags(section: &mut Section, options: &TagOptions) {
+    let mut index = 0;
+    while index < section.lines.len() {
+        let line = &section.lines[index];
+        let indent = line.len() - line.trim_start().len();
+        let Some(item) = line.trim_start().strip_prefix("• ") else {
+            index += 1;
+            continue;
+        };
+        let (tags, rest) = split_media_tags(item);
+        if tags.is_empty() {
+            index += 1;
+            continue;
+        }
+        if options.excludes(&tags) {
+            let end = section.lines[index + 1..]
+                .iter()
+                .position(|next| {
+                    let next_indent = next.len() - next.trim_start().len();
+                    next_indent <= indent || !next.trim_start().starts_with("• ")
+                })
+                .map_or(section.lines.len(), |offset| index + 1 + offset);
+            section.replace_lines(index..end, Vec::new());
+            continue;
+        }
+        let prefix: Vec<&str> = tags
+            .iter()
+            .map(|tag| options.icon(&tag.name).unwrap_or(tag.raw))
+            .collect();
+        let rendered = format!("{}• {} {rest}", &line[..indent], prefix.join(" "));
+        section.lines[index] = rendered;
+        index += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sections;
+
+    const ISSUE: &str = "## Observations/Thoughts\n* [audio] [Netstack.FM](https://netstack.fm/)\n* [video] [ZH] [Rust 中文](https://example.com/zh)\n    * [slides](https://example.com/slides)\n* [Untagged](https://example.com/)\n* [livestream] [Live](https://example.com/live)\n";
+
+    #[test]
+    fn splits_escaped_tags() {
+        let (tags, rest) = split_media_tags("\\[video\\] \\[ZH\\] [Rust](https://x)");
+        let names: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["video", "zh"]);
+        assert_eq!(tags[1].raw, "\\[ZH\\]");
+        assert_eq!(rest, "[Rust](https://x)");
+        assert!(split_media_tags("[Rust](https://x)").0.is_empty());
+    }
+
+    #[test]
+    fn known_tags_become_icons() {
+        let mut section = parse_sections(ISSUE).remove(0);
+        let options = TagOptions {
+            icons: BTreeMap::from([("livestream".to_string(), "📡".to_string())]),
+            ..TagOptions::default()
+        };
+        apply_media_tags(&mut section, &options);
+        assert_eq!(
+            section.lines,
+            vec![
+                "• 🎧 [Netstack\\.FM](https://netstack.fm/)",
+                "• 🎬 🇨🇳 [Rust 中文](https://example.com/zh)",
+                "  • [slides](https://example.com/slides)",
+                "• [Untagged](https://example.com/)",
+                "• 📡 [Live](https://example.com/live)",
+            ]
+        );
+    }
+
+    #[test]
+    fn excluded_tags_drop_items_with_children() {
+        let mut section = parse_sections(ISSUE).remove(0);
+        let options = TagOptions {
+            exclude: vec!["ZH".to_string()],
+            ..TagOptions::default()
+        };
+        apply_media_tags(&mut section, &options);
+        assert_eq!(section.lines.len(), 3);
+        assert!(section.lines[0].starts_with("• 🎧"));
+        assert!(section.lines[1].starts_with("• [Untagged]"));
+        assert_eq!(
+            section.lines[2],
+            "• \\[livestream\\] [Live](https://example.com/live)"
+        );
+    }
+}
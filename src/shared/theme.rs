@@ -0,0 +1,288 @@
+use phf::phf_map;
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, io, path::Path, sync::LazyLock};
+use teloxide::utils::markdown::escape;
+
+use crate::generator::{SUBHEADING_EMOJIS, escape_markdown_url};
+use crate::parser::Section;
+use crate::shared::glossary::Glossary;
+use crate::shared::locale::Messages;
+
+/// Mapping of section titles to the emojis framing their headings.
+pub static SECTION_EMOJIS: phf::Map<&'static str, &'static str> = phf_map! {
+    "upcoming events" => "🎉",
+    "crate of the week" => "📦",
+    "final comment period" => "⚖️",
+};
+
+/// Theme used when no other is configured.
+pub(crate) static DEFAULT_THEME: LazyLock<Theme> = LazyLock::new(Theme::default);
+
+/// Letter case of section headings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeadingCase {
+    /// `UPCOMING EVENTS`
+    #[default]
+    Upper,
+    /// `Upcoming Events`
+    Title,
+    /// As written in the issue.
+    Original,
+}
+
+/// Markup applied to heading text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeadingStyle {
+    #[default]
+    Bold,
+    Underline,
+    Plain,
+}
+
+/// Look of section headings and subheadings, usually loaded per channel.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// Case of section headings; subheadings keep the issue's case.
+    pub case: HeadingCase,
+    pub style: HeadingStyle,
+    /// Emoji for section headings without an entry in `headings`; empty for
+    /// none.
+    pub emoji: String,
+    /// Emojis for section headings, consulted before the built-in ones.
+    pub headings: BTreeMap<String, String>,
+    /// Emojis for subheadings, consulted before the built-in ones.
+    pub subheadings: BTreeMap<String, String>,
+    /// Line placed below every section heading, e.g. `━━━━━━━━`.
+    pub separator: Option<String>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            case: HeadingCase::Upper,
+            style: HeadingStyle::Bold,
+            emoji: "📰".to_string(),
+            headings: BTreeMap::new(),
+            subheadings: BTreeMap::new(),
+            separator: None,
+        }
+    }
+}
+
+/// Find `title` in `custom` ignoring case, falling back to `builtin`.
+fn lookup<'a>(
+    custom: &'a BTreeMap<String, String>,
+    builtin: &'static phf::Map<&'static str, &'static str>,
+    title: &str,
+) -> Option<&'a str> {
+    custom
+        .iter()
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case(title))
+        .map(|(_, emoji)| emoji.as_str())
+        .or_else(|| builtin.get(title).copied())
+}
+
+fn title_case(title: &str) -> String {
+    title
+        .split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl Theme {
+    fn wrap(&self, text: &str) -> String {
+        match self.style {
+            HeadingStyle::Bold => format!("**{text}**"),
+            HeadingStyle::Underline => format!("__{text}__"),
+            HeadingStyle::Plain => text.to_string(),
+        }
+    }
+
+    /// Format a section heading for Telegram posts.
+    ///
+    /// # Parameters
+    /// - `title`: Raw section title text.
+    ///
+    /// # Returns
+    /// The styled heading framed by its emoji, followed by the separator.
+    pub fn heading(&self, title: &str) -> String {
+        self.heading_as(title, title, &Glossary::default())
+    }
+
+    /// Format the heading of section `title` showing `label` instead, e.g. a
+    /// translation; the emoji is still chosen by `title` and terms of
+    /// `glossary` keep their case.
+    pub(crate) fn heading_as(&self, title: &str, label: &str, glossary: &Glossary) -> String {
+        let lower = title.trim().to_lowercase();
+        let emoji = lookup(&self.headings, &SECTION_EMOJIS, &lower).unwrap_or(&self.emoji);
+        let text = match self.case {
+            HeadingCase::Upper => glossary.map_unprotected(label, str::to_uppercase),
+            HeadingCase::Title => glossary.map_unprotected(label, title_case),
+            HeadingCase::Original => label.to_string(),
+        };
+        let text = self.wrap(&escape(&text));
+        let mut heading = if emoji.is_empty() {
+            text
+        } else {
+            format!("{emoji} {text} {emoji}")
+        };
+        if let Some(separator) = &self.separator {
+            heading.push('\n');
+            heading.push_str(&escape(separator));
+        }
+        heading
+    }
+
+    /// Format a level 3 or level 4 heading.
+    ///
+    /// # Parameters
+    /// - `title`: Heading text, possibly a `[text](url)` link.
+    ///
+    /// # Returns
+    /// The escaped, styled heading; headings with an emoji are set apart by
+    /// an empty line.
+    pub fn subheading(&self, title: &str) -> String {
+        self.subheading_as(title, title)
+    }
+
+    /// Format subheading `title` showing `label` instead, e.g. a
+    /// translation; the emoji is still chosen by `title`.
+    pub(crate) fn subheading_as(&self, title: &str, label: &str) -> String {
+        let trimmed = label.trim();
+        if trimmed.starts_with('[')
+            && trimmed.ends_with(')')
+            && let Some(idx) = trimmed.find("](")
+        {
+            let text = &trimmed[1..idx];
+            let url = &trimmed[idx + 2..trimmed.len() - 1];
+            return self.wrap(&format!("[{}]({})", escape(text), escape_markdown_url(url)));
+        }
+        let lower = title.trim().to_ascii_lowercase();
+        if lower == "quote of the week" {
+            let emoji = lookup(&self.subheadings, &SUBHEADING_EMOJIS, &lower).unwrap_or("💬");
+            let text = self.wrap(&format!("{}:", escape(trimmed)));
+            return format!("\n{text} {emoji}\n");
+        }
+        match lookup(&self.subheadings, &SUBHEADING_EMOJIS, &lower) {
+            Some(emoji) => format!("\n{} {emoji}", self.wrap(&format!("{}:", escape(trimmed)))),
+            None => self.wrap(&escape(trimmed)),
+        }
+    }
+}
+
+/// Read a [`Theme`] from the TOML file at `path`.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or does not match the
+/// expected schema.
+pub fn load_theme(path: &Path) -> io::Result<Theme> {
+    let text = fs::read_to_string(path)?;
+    toml::from_str(&text)
+        .map_err(|e| io::Error::other(format!("invalid theme {}: {e}", path.display())))
+}
+
+/// Re-render the subheadings of `section` with `theme`, translating their
+/// titles with `messages`.
+///
+/// Link subheadings are restyled; other lines that no longer hold the
+/// default rendering of their subheading, for example after a section
+/// transform, are left alone.
+pub(crate) fn apply_theme(section: &mut Section, theme: &Theme, messages: &Messages) {
+    for sub in &section.subheadings {
+        let label = messages.title(&sub.title);
+        if *theme == *DEFAULT_THEME && label == sub.title {
+            continue;
+        }
+        let Some(line) = section.lines.get_mut(sub.line) else {
+            continue;
+        };
+        if *line == DEFAULT_THEME.subheading(&sub.title) {
+            *line = theme.subheading_as(&sub.title, label);
+        } else if let Some(link) = line
+            .strip_prefix("**[")
+            .and_then(|rest| rest.strip_suffix("**"))
+        {
+            *line = theme.wrap(&format!("[{link}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sections;
+    use crate::shared::locale::Locale;
+
+    #[test]
+    fn default_theme_keeps_classic_look() {
+        let theme = Theme::default();
+        assert_eq!(
+            theme.heading("Crate of the Week"),
+            "📦 **CRATE OF THE WEEK** 📦"
+        );
+        assert_eq!(theme.heading("Updates"), "📰 **UPDATES** 📰");
+        assert_eq!(theme.subheading("Compiler"), "\n**Compiler:** 🛠️");
+        assert_eq!(theme.subheading("Other"), "**Other**");
+    }
+
+    #[test]
+    fn theme_file_changes_headings() {
+        let theme: Theme = toml::from_str(
+            r#"
+case = "title"
+style = "underline"
+emoji = ""
+separator = "━━━━━━"
+headings = { "Crate of the Week" = "🦀" }
+subheadings = { other = "✨" }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            theme.heading("crate of the week"),
+            "🦀 __Crate Of The Week__ 🦀\n━━━━━━"
+        );
+        assert_eq!(theme.heading("Jobs"), "__Jobs__\n━━━━━━");
+        assert_eq!(theme.subheading("Other"), "\n__Other:__ ✨");
+        assert_eq!(
+            theme.subheading("[Rust](https://example.com/)"),
+            "__[Rust](https://example.com/)__"
+        );
+    }
+
+    #[test]
+    fn glossary_terms_keep_their_case() {
+        let glossary = Glossary {
+            terms: vec!["rust-analyzer".to_string()],
+        };
+        assert_eq!(
+            Theme::default().heading_as("News", "News from rust-analyzer", &glossary),
+            "📰 **NEWS FROM rust\\-analyzer** 📰"
+        );
+    }
+
+    #[test]
+    fn subheadings_are_rerendered() {
+        let mut section = parse_sections(
+            "## Updates\n### Compiler\n* [a](https://a.example)\n### [Rust](https://r.example)\n",
+        )
+        .remove(0);
+        let theme = Theme {
+            style: HeadingStyle::Plain,
+            ..Theme::default()
+        };
+        apply_theme(&mut section, &theme, Locale::En.messages());
+        assert_eq!(section.lines[0], "\nCompiler: 🛠️");
+        assert_eq!(section.lines[2], "[Rust](https://r.example)");
+    }
+}
@@ -0,0 +1,74 @@
+use serde::Deserialize;
+
+/// Which message the follow-up parts of an issue reply to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplyMode {
+    /// Send every part as an independent message.
+    #[default]
+    None,
+    /// Reply to the first part.
+    First,
+    /// Reply to the part sent just before.
+    Previous,
+}
+
+/// Settings that group the posts of an issue into a thread.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThreadingOptions {
+    /// Message parts 2..N reply to.
+    pub reply_to: ReplyMode,
+    /// Forum topic of the production supergroup that receives all messages;
+    /// the CLI reads the developer topic from `DEV_THREAD_ID`.
+    pub message_thread_id: Option<i64>,
+}
+
+impl ThreadingOptions {
+    /// Message the next part replies to.
+    ///
+    /// # Parameters
+    /// - `sent`: Identifiers of the parts delivered so far, in order.
+    ///
+    /// # Returns
+    /// `None` for the first part or when replies are off.
+    pub fn reply_target(&self, sent: &[i64]) -> Option<i64> {
+        match self.reply_to {
+            ReplyMode::None => None,
+            ReplyMode::First => sent.first().copied(),
+            ReplyMode::Previous => sent.last().copied(),
+        }
+    }
+
+    /// Render the `reply_parameters` field of a `sendMessage` request.
+    pub(crate) fn reply_parameters(&self, sent: &[i64]) -> Option<String> {
+        self.reply_target(sent).map(|message_id| {
+            serde_json::json!({
+                "message_id": message_id,
+                "allow_sending_without_reply": true,
+            })
+            .to_string()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replies_follow_the_mode() {
+        let sent = [10, 11, 12];
+        let mut options = ThreadingOptions::default();
+        assert_eq!(options.reply_target(&sent), None);
+        options.reply_to = ReplyMode::First;
+        assert_eq!(options.reply_target(&sent), Some(10));
+        assert_eq!(options.reply_target(&[]), None);
+        options.reply_to = ReplyMode::Previous;
+        assert_eq!(options.reply_target(&sent), Some(12));
+        assert_eq!(
+            options.reply_parameters(&sent).as_deref(),
+            Some(r#"{"allow_sending_without_reply":true,"message_id":12}"#)
+        );
+    }
+}
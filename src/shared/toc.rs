@@ -0,0 +1,162 @@
+use serde::Deserialize;
+use teloxide::utils::markdown::escape;
+
+use crate::generator::{escape_markdown_url, normalize_chat_id};
+use crate::shared::locale::Messages;
+
+/// Settings of the table of contents sent after the issue posts.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TocOptions {
+    /// Append the contents to the first post with `editMessageText` instead
+    /// of sending a post of its own; falls back to a post when the first
+    /// post would grow past the Telegram limit.
+    pub edit_first: bool,
+}
+
+/// A section heading and the post it starts in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    /// Section title as shown to readers, e.g. the localized title.
+    pub title: String,
+    /// Index of the post in the generated posts.
+    pub post: usize,
+}
+
+/// Find the post of every heading, in order.
+///
+/// `headings` pairs the first line of a rendered heading with its title;
+/// headings are searched from the post of the previous one on and those
+/// that are not found are left out.
+pub(crate) fn locate_headings(posts: &[String], headings: &[(String, String)]) -> Vec<TocEntry> {
+    let mut entries = Vec::new();
+    let mut post = 0;
+    for (heading, title) in headings {
+        let found = posts
+            .iter()
+            .enumerate()
+            .skip(post)
+            .find(|(_, text)| text.lines().any(|line| line == heading));
+        if let Some((index, _)) = found {
+            post = index;
+            entries.push(TocEntry {
+                title: title.clone(),
+                post: index,
+            });
+        }
+    }
+    entries
+}
+
+/// Build the public link of a message.
+///
+/// # Parameters
+/// - `chat_id`: Channel username such as `@rust_digest` or numeric chat
+///   identifier, normalized with [`normalize_chat_id`].
+/// - `message_id`: Identifier returned by Telegram.
+///
+/// # Returns
+/// `https://t.me/<username>/<id>` for public chats, `https://t.me/c/<chat>/<id>`
+/// for private supergroups and channels, or `None` for other chats.
+pub fn message_link(chat_id: &str, message_id: i64) -> Option<String> {
+    let chat_id = normalize_chat_id(chat_id);
+    if let Some(username) = chat_id.strip_prefix('@') {
+        return (!username.is_empty()).then(|| format!("https://t.me/{username}/{message_id}"));
+    }
+    let internal = chat_id.strip_prefix("-100")?;
+    (!internal.is_empty() && internal.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| format!("https://t.me/c/{internal}/{message_id}"))
+}
+
+/// Render the table of contents as a Telegram Markdown post.
+///
+/// # Parameters
+/// - `entries`: Headings located in the posts, see [`crate::generator::generate_toc`].
+/// - `message_ids`: Identifiers of the delivered posts, in post order.
+/// - `chat_id`: Chat the posts were delivered to.
+/// - `messages`: Labels of the delivery locale.
+///
+/// # Returns
+/// `None` when no entry can be linked.
+pub fn render_toc(
+    entries: &[TocEntry],
+    message_ids: &[i64],
+    chat_id: &str,
+    messages: &Messages,
+) -> Option<String> {
+    let lines: Vec<String> = entries
+        .iter()
+        .filter_map(|entry| {
+            let link = message_link(chat_id, *message_ids.get(entry.post)?)?;
+            Some(format!(
+                "• [{}]({})",
+                escape(&entry.title),
+                escape_markdown_url(&link)
+            ))
+        })
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "**{}**\n{}\n",
+        escape(messages.contents),
+        lines.join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::locale::Locale;
+
+    #[test]
+    fn links_public_and_private_chats() {
+        assert_eq!(
+            message_link("@rust_digest", 42).as_deref(),
+            Some("https://t.me/rust_digest/42")
+        );
+        assert_eq!(
+            message_link("-1001234567", 42).as_deref(),
+            Some("https://t.me/c/1234567/42")
+        );
+        assert_eq!(
+            message_link("1234567", 7).as_deref(),
+            Some("https://t.me/c/1234567/7")
+        );
+        assert_eq!(message_link("-100abc", 7), None);
+    }
+
+    #[test]
+    fn locates_headings_in_order() {
+        let posts = vec![
+            "Header\n📰 **OFFICIAL** 📰\n• a\n".to_string(),
+            "*Part 2/2*\n\n📰 **JOBS** 📰\n📰 **OFFICIAL** 📰\n".to_string(),
+        ];
+        let headings = vec![
+            ("📰 **OFFICIAL** 📰".to_string(), "Official".to_string()),
+            ("📰 **MISSING** 📰".to_string(), "Missing".to_string()),
+            ("📰 **JOBS** 📰".to_string(), "Jobs".to_string()),
+        ];
+        let entries = locate_headings(&posts, &headings);
+        assert_eq!(
+            entries,
+            vec![
+                TocEntry {
+                    title: "Official".into(),
+                    post: 0
+                },
+                TocEntry {
+                    title: "Jobs".into(),
+                    post: 1
+                },
+            ]
+        );
+        let toc = render_toc(&entries, &[10, 11], "@rust_digest", Locale::En.messages()).unwrap();
+        assert_eq!(
+            toc,
+            "**Contents**\n• [Official](https://t.me/rust_digest/10)\n• [Jobs](https://t.me/rust_digest/11)\n"
+        );
+        assert!(render_toc(&entries, &[], "@rust_digest", Locale::En.messages()).is_none());
+    }
+}
@@ -0,0 +1,431 @@
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use teloxide::utils::markdown::escape;
+
+use crate::generator::escape_markdown_url;
+use crate::parser::{Section, Subheading};
+use crate::shared::config::GenerationOptions;
+use crate::shared::jobs::simplify_jobs_section;
+use crate::shared::locale::Messages;
+use crate::shared::quote::QUOTE_SECTION;
+use crate::shared::theme::DEFAULT_THEME;
+
+/// Name of the built-in transform shortening the Jobs section.
+pub const JOBS_TRANSFORM: &str = "jobs";
+/// Name of the built-in transform cleaning up the Call for Participation.
+pub const CFP_TRANSFORM: &str = "cfp";
+/// Name of the built-in transform formatting the Quote of the Week.
+pub const QUOTE_TRANSFORM: &str = "quote";
+
+/// A rewrite of a parsed section before it is rendered, e.g. to drop
+/// boilerplate.
+///
+/// Closures taking `(&mut Section, &Messages)` implement this trait.
+pub trait SectionTransform {
+    /// Rewrite `section`; text added to it should come from `messages`.
+    fn apply(&self, section: &mut Section, messages: &Messages);
+}
+
+impl<F: Fn(&mut Section, &Messages)> SectionTransform for F {
+    fn apply(&self, section: &mut Section, messages: &Messages) {
+        self(section, messages)
+    }
+}
+
+/// Settings of the section transforms.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransformOptions {
+    /// Names of transforms to skip, e.g. `quote`.
+    pub disable: Vec<String>,
+}
+
+struct Entry {
+    name: String,
+    title: Regex,
+    order: i32,
+    transform: Box<dyn SectionTransform>,
+}
+
+/// Ordered set of section transforms selected by section title.
+#[derive(Default)]
+pub struct TransformRegistry {
+    entries: Vec<Entry>,
+}
+
+impl TransformRegistry {
+    /// Create a registry without any transform.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry holding the built-in `jobs`, `cfp` and `quote`
+    /// transforms at orders 100, 200 and 300 with default settings.
+    pub fn with_builtins() -> Self {
+        Self::with_builtins_for(&GenerationOptions::default())
+    }
+
+    /// Create a registry holding the built-in transforms configured by
+    /// `options`, e.g. the `[jobs]` resources.
+    pub fn with_builtins_for(options: &GenerationOptions) -> Self {
+        let mut registry = Self::new();
+        let jobs = options.jobs.clone();
+        registry
+            .register(
+                JOBS_TRANSFORM,
+                "Jobs",
+                100,
+                move |section: &mut Section, messages: &Messages| {
+                    simplify_jobs_section(section, messages, &jobs)
+                },
+            )
+            .expect("built-in title patterns are valid");
+        registry
+            .register(
+                CFP_TRANSFORM,
+                "Call for Participation; projects and speakers",
+                200,
+                simplify_cfp_section,
+            )
+            .expect("built-in title patterns are valid");
+        let detach = options.quote.detached();
+        registry
+            .register(
+                QUOTE_TRANSFORM,
+                ".*",
+                300,
+                move |section: &mut Section, _: &Messages| simplify_quote_section(section, detach),
+            )
+            .expect("built-in title patterns are valid");
+        registry
+    }
+
+    /// Register `transform` for sections whose whole title matches the
+    /// case-insensitive regular expression `title`.
+    ///
+    /// Transforms run by ascending `order`, equal orders in registration
+    /// order. A transform registered under an existing name replaces it.
+    ///
+    /// # Errors
+    /// Returns an error if `title` is not a valid regular expression.
+    pub fn register(
+        &mut self,
+        name: &str,
+        title: &str,
+        order: i32,
+        transform: impl SectionTransform + 'static,
+    ) -> Result<(), regex::Error> {
+        let title = RegexBuilder::new(&format!("^(?:{title})$"))
+            .case_insensitive(true)
+            .build()?;
+        self.entries.retain(|entry| entry.name != name);
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| entry.order > order)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(
+            index,
+            Entry {
+                name: name.to_string(),
+                title,
+                order,
+                transform: Box::new(transform),
+            },
+        );
+        Ok(())
+    }
+
+    /// Names of the registered transforms in the order they run.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.name.as_str())
+    }
+
+    /// Run the transforms matching the title of `section`, skipping those
+    /// named in `disabled`.
+    pub fn apply(&self, section: &mut Section, messages: &Messages, disabled: &[String]) {
+        for entry in &self.entries {
+            if entry.title.is_match(section.title.trim())
+                && !disabled
+                    .iter()
+                    .any(|name| name.trim().eq_ignore_ascii_case(&entry.name))
+            {
+                entry.transform.apply(section, messages);
+            }
+        }
+    }
+}
+
+/// Short URL guiding contributors how to submit CFP tasks.
+const CFP_GUIDELINES: &str =
+    "https://github.com/rust-lang/this-week-in-rust#call-for-participation-guidelines";
+
+fn simplify_cfp_section(section: &mut Section, messages: &Messages) {
+    let mut cleaned = Vec::new();
+    let mut kept = Vec::new();
+    let mut in_projects = false;
+    let mut has_task = false;
+
+    for (index, line) in section.lines.iter().enumerate() {
+        if line.starts_with("**CFP \\- Projects**") {
+            in_projects = true;
+            kept.push((index, cleaned.len()));
+            cleaned.push(line.clone());
+            continue;
+        }
+        if line.starts_with("**CFP \\- Events**") {
+            in_projects = false;
+            kept.push((index, cleaned.len()));
+            cleaned.push(line.clone());
+            continue;
+        }
+        if line.contains("guidelines") && line.contains("submit tasks") {
+            continue;
+        }
+        if line.starts_with("Always wanted to contribute")
+            || line.starts_with("Some of these tasks")
+            || line.starts_with("Are you a new or experienced speaker")
+        {
+            continue;
+        }
+        if in_projects {
+            if line.trim() == "No Calls for participation were submitted this week." {
+                continue;
+            }
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('•') || trimmed.starts_with('*') {
+                has_task = true;
+            }
+        }
+        kept.push((index, cleaned.len()));
+        cleaned.push(line.clone());
+    }
+
+    if !has_task {
+        let msg = format!(
+            "{} [{}]({})",
+            escape(messages.no_cfp_tasks),
+            escape(messages.instructions),
+            escape_markdown_url(CFP_GUIDELINES)
+        );
+        cleaned.push(msg);
+    }
+
+    replace_kept_lines(section, cleaned, &kept);
+}
+
+/// Replace the lines of `section` with `lines`, where `kept` pairs the index
+/// of every source line that was kept with its new index; subheadings follow
+/// their lines and those removed are dropped.
+fn replace_kept_lines(section: &mut Section, lines: Vec<String>, kept: &[(usize, usize)]) {
+    section
+        .subheadings
+        .retain_mut(|sub| match kept.iter().find(|(old, _)| *old == sub.line) {
+            Some(&(_, new)) => {
+                sub.line = new;
+                true
+            }
+            None => false,
+        });
+    section.lines = lines;
+}
+
+/// Format the quote as italic lines, or with `detach` remove it because it
+/// is published on its own.
+fn simplify_quote_section(section: &mut Section, detach: bool) {
+    let mut cleaned = Vec::new();
+    let mut kept = Vec::new();
+    let mut in_quote_section = section.title.eq_ignore_ascii_case(QUOTE_SECTION);
+    // Only the first paragraph of a quote keeps its `>`; a detached quote is
+    // therefore removed up to its attribution when it has one.
+    let mut awaiting_attribution = false;
+    let mut heading = None;
+    for (index, line) in section.lines.iter().enumerate() {
+        if line.contains(QUOTE_SECTION) {
+            if !detach {
+                // Styled with the configured theme and locale by `apply_theme`.
+                if !section.subheadings.iter().any(|sub| sub.line == index) {
+                    heading = Some(cleaned.len());
+                }
+                kept.push((index, cleaned.len()));
+                cleaned.push(DEFAULT_THEME.subheading(QUOTE_SECTION));
+            }
+            in_quote_section = true;
+            awaiting_attribution = detach
+                && section.lines[index + 1..]
+                    .iter()
+                    .any(|line| line.trim_start().starts_with('–'));
+            continue;
+        }
+        if !in_quote_section {
+            kept.push((index, cleaned.len()));
+            cleaned.push(line.clone());
+            continue;
+        }
+        if awaiting_attribution {
+            awaiting_attribution = !line.trim_start().starts_with('–');
+            if !awaiting_attribution && cleaned.last().is_some_and(|l| !l.is_empty()) {
+                cleaned.push(String::new());
+            }
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            if cleaned.last().is_some_and(|l| !l.is_empty()) {
+                cleaned.push(String::new());
+            }
+            continue;
+        }
+
+        let lower = trimmed.to_ascii_lowercase();
+        if lower.contains("please submit quotes")
+            || lower.starts_with("thanks to")
+            || lower.starts_with("despite ")
+        {
+            continue;
+        }
+
+        if trimmed.starts_with('–') {
+            if !detach {
+                cleaned.push(trimmed.to_string());
+                cleaned.push(String::new());
+            }
+            continue;
+        }
+
+        let content = trimmed
+            .trim_start_matches("\\>")
+            .trim_start_matches('>')
+            .trim_start();
+        if content.len() != trimmed.len() {
+            if detach {
+                continue;
+            }
+            if !content.is_empty() {
+                cleaned.push(format!("_{content}_"));
+            } else if cleaned.last().is_some_and(|l| !l.is_empty()) {
+                cleaned.push(String::new());
+            }
+            continue;
+        }
+
+        kept.push((index, cleaned.len()));
+        cleaned.push(line.clone());
+    }
+    replace_kept_lines(section, cleaned, &kept);
+    if let Some(line) = heading {
+        let at = section
+            .subheadings
+            .iter()
+            .position(|sub| sub.line > line)
+            .unwrap_or(section.subheadings.len());
+        section.subheadings.insert(
+            at,
+            Subheading {
+                title: QUOTE_SECTION.to_string(),
+                level: 1,
+                line,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sections;
+    use crate::shared::locale::Locale;
+    use crate::shared::theme::{Theme, apply_theme};
+
+    fn section(title: &str) -> Section {
+        parse_sections(&format!("## {title}\n* item\n")).remove(0)
+    }
+
+    fn append(marker: &'static str) -> impl Fn(&mut Section, &Messages) {
+        move |section: &mut Section, _: &Messages| section.lines.push(marker.to_string())
+    }
+
+    #[test]
+    fn transforms_run_by_order_and_title() {
+        let mut registry = TransformRegistry::new();
+        registry
+            .register("late", "news|jobs", 20, append("late"))
+            .unwrap();
+        registry
+            .register("early", "News", 10, append("early"))
+            .unwrap();
+        registry
+            .register("other", "Other", 0, append("other"))
+            .unwrap();
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["other", "early", "late"]
+        );
+
+        let mut news = section("News");
+        registry.apply(&mut news, Locale::En.messages(), &[]);
+        assert_eq!(news.lines, ["• item", "early", "late"]);
+
+        let mut jobs = section("Jobs");
+        registry.apply(&mut jobs, Locale::En.messages(), &["LATE".to_string()]);
+        assert_eq!(jobs.lines, ["• item"]);
+    }
+
+    #[test]
+    fn builtins_can_be_replaced_or_disabled() {
+        let mut registry = TransformRegistry::with_builtins();
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["jobs", "cfp", "quote"]
+        );
+        let mut jobs = section("Jobs");
+        registry.apply(
+            &mut jobs,
+            Locale::En.messages(),
+            &[JOBS_TRANSFORM.to_string()],
+        );
+        assert_eq!(jobs.lines, ["• item"]);
+
+        registry
+            .register(JOBS_TRANSFORM, "Jobs", 100, append("custom"))
+            .unwrap();
+        registry.apply(&mut jobs, Locale::En.messages(), &[]);
+        assert_eq!(jobs.lines, ["• item", "custom"]);
+        assert!(registry.register("bad", "(", 0, append("x")).is_err());
+    }
+
+    #[test]
+    fn cleanups_keep_subheading_lines() {
+        let mut cfp = parse_sections(
+            "## Call for Participation; projects and speakers\n### CFP - Projects\nAlways wanted to contribute?\n\nSome of these tasks\n* [Task](https://example.com/1)\n### CFP - Events\nAre you a new or experienced speaker?\n* [Talk](https://example.com/2)\n",
+        )
+        .remove(0);
+        simplify_cfp_section(&mut cfp, Locale::En.messages());
+        assert_eq!(cfp.subheadings.len(), 2);
+        for sub in &cfp.subheadings {
+            assert_eq!(cfp.lines[sub.line], DEFAULT_THEME.subheading(&sub.title));
+        }
+
+        let mut jobs = parse_sections(
+            "## Jobs\nHiring\n# Quote of the Week\n> Quoted\n\n– [Jane](https://example.com/q)\n\nThanks to someone\n### Later\n* item\n",
+        )
+        .remove(0);
+        simplify_quote_section(&mut jobs, false);
+        let titles: Vec<_> = jobs
+            .subheadings
+            .iter()
+            .map(|sub| sub.title.as_str())
+            .collect();
+        assert_eq!(titles, [QUOTE_SECTION, "Later"]);
+        for sub in &jobs.subheadings {
+            assert_eq!(jobs.lines[sub.line], DEFAULT_THEME.subheading(&sub.title));
+        }
+        let theme: Theme = toml::from_str("style = \"underline\"").unwrap();
+        apply_theme(&mut jobs, &theme, Locale::En.messages());
+        assert_eq!(
+            jobs.lines[jobs.subheadings[0].line],
+            theme.subheading(QUOTE_SECTION)
+        );
+    }
+}
@@ -0,0 +1,561 @@
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use teloxide::utils::markdown::escape;
+
+use crate::parser::{Section, unescape_telegram_markdown};
+use crate::shared::glossary::Glossary;
+
+/// Environment variable consulted when `[translate]` has no `api_key`.
+pub const TRANSLATE_API_KEY_ENV: &str = "TRANSLATE_API_KEY";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Translates plain text between the languages it was set up for.
+pub trait Translator {
+    /// Translate `text`, which carries no Markdown; links, code and
+    /// formatting markers are replaced by `{0}`, `{1}`, … placeholders that
+    /// must be kept.
+    ///
+    /// # Errors
+    /// Returns an error when no translation is available.
+    fn translate(&self, text: &str) -> io::Result<String>;
+
+    /// Persist state such as a cache once all text is translated.
+    ///
+    /// # Errors
+    /// Returns an error if the state cannot be written.
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Settings of the translation stage.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TranslateOptions {
+    /// LibreTranslate-compatible `/translate` URL; without it only the
+    /// translation memory is used.
+    pub endpoint: Option<String>,
+    /// Language of the issue.
+    pub source: String,
+    /// Language of the posts.
+    pub target: String,
+    /// Key sent with every request, falling back to `TRANSLATE_API_KEY`.
+    pub api_key: Option<String>,
+    /// JSON file caching translations for `target`.
+    pub memory: Option<PathBuf>,
+}
+
+impl Default for TranslateOptions {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            source: "en".to_string(),
+            target: "ru".to_string(),
+            api_key: None,
+            memory: None,
+        }
+    }
+}
+
+impl TranslateOptions {
+    /// Build the translator described by these options.
+    ///
+    /// # Errors
+    /// Returns an error if neither an endpoint nor a memory file is set or
+    /// the memory file cannot be read.
+    pub fn translator(&self) -> io::Result<Box<dyn Translator>> {
+        let backend = self.endpoint.as_ref().map(|endpoint| {
+            let api_key = self
+                .api_key
+                .clone()
+                .or_else(|| env::var(TRANSLATE_API_KEY_ENV).ok());
+            Box::new(HttpTranslator::new(
+                endpoint,
+                &self.source,
+                &self.target,
+                api_key,
+            )) as Box<dyn Translator>
+        });
+        match (&self.memory, backend) {
+            (Some(path), backend) => Ok(Box::new(TranslationMemory::open(path, backend)?)),
+            (None, Some(backend)) => Ok(backend),
+            (None, None) => Err(io::Error::other(
+                "[translate] needs an endpoint or a memory file",
+            )),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TranslateRequest<'a> {
+    q: &'a str,
+    source: &'a str,
+    target: &'a str,
+    format: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct TranslateResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: Option<String>,
+    error: Option<String>,
+}
+
+/// Translator calling a LibreTranslate-compatible HTTP endpoint.
+pub struct HttpTranslator {
+    client: Client,
+    endpoint: String,
+    source: String,
+    target: String,
+    api_key: Option<String>,
+}
+
+impl HttpTranslator {
+    /// Create a translator posting to `endpoint`.
+    pub fn new(endpoint: &str, source: &str, target: &str, api_key: Option<String>) -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| Client::new());
+        Self {
+            client,
+            endpoint: endpoint.to_string(),
+            source: source.to_string(),
+            target: target.to_string(),
+            api_key,
+        }
+    }
+}
+
+impl Translator for HttpTranslator {
+    fn translate(&self, text: &str) -> io::Result<String> {
+        let request = TranslateRequest {
+            q: text,
+            source: &self.source,
+            target: &self.target,
+            format: "text",
+            api_key: self.api_key.as_deref(),
+        };
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .json(&request)
+            .send()
+            .map_err(io::Error::other)?;
+        let status = response.status();
+        let body: TranslateResponse = response.json().map_err(io::Error::other)?;
+        match (status.is_success(), body.translated_text) {
+            (true, Some(text)) => Ok(text),
+            _ => Err(io::Error::other(format!(
+                "translation failed with status {status}: {}",
+                body.error.unwrap_or_default()
+            ))),
+        }
+    }
+}
+
+/// File-based cache of translations in front of an optional backend.
+///
+/// Without a backend only cached texts are translated, which keeps repeated
+/// runs and tests offline and deterministic.
+pub struct TranslationMemory {
+    path: PathBuf,
+    entries: RefCell<BTreeMap<String, String>>,
+    backend: Option<Box<dyn Translator>>,
+}
+
+impl TranslationMemory {
+    /// Load the memory at `path`; a missing file starts an empty memory.
+    ///
+    /// # Errors
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn open(path: &Path, backend: Option<Box<dyn Translator>>) -> io::Result<Self> {
+        let entries = match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| {
+                io::Error::other(format!(
+                    "invalid translation memory {}: {e}",
+                    path.display()
+                ))
+            })?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries: RefCell::new(entries),
+            backend,
+        })
+    }
+}
+
+impl Translator for TranslationMemory {
+    fn translate(&self, text: &str) -> io::Result<String> {
+        if let Some(cached) = self.entries.borrow().get(text) {
+            return Ok(cached.clone());
+        }
+        let Some(backend) = &self.backend else {
+            return Err(io::Error::other(format!(
+                "no cached translation for {text:?}"
+            )));
+        };
+        let translated = backend.translate(text)?;
+        self.entries
+            .borrow_mut()
+            .insert(text.to_string(), translated.clone());
+        Ok(translated)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        if self.backend.is_none() {
+            return Ok(());
+        }
+        let mut json =
+            serde_json::to_string_pretty(&*self.entries.borrow()).map_err(io::Error::other)?;
+        json.push('\n');
+        fs::write(&self.path, json)
+    }
+}
+
+/// Characters that may open a translatable text, besides letters and digits.
+const TEXT_OPENERS: &str = "([{\"'«“¿¡#$@";
+
+/// Escaped text of a line in which the spans kept as written are replaced
+/// by `{n}` placeholders, so the translator sees whole sentences.
+#[derive(Default)]
+struct Template {
+    text: String,
+    /// Escaped Markdown of each placeholder, in order.
+    spans: Vec<String>,
+    /// Whether the last placeholder holds formatting markers only.
+    after_marker: bool,
+}
+
+impl Template {
+    fn push_text(&mut self, text: &str) {
+        self.text.push_str(text);
+        self.after_marker = false;
+    }
+
+    fn push_span(&mut self, span: String) {
+        self.text.push_str(&format!("{{{}}}", self.spans.len()));
+        self.spans.push(span);
+        self.after_marker = false;
+    }
+
+    /// Add a formatting marker, merging adjacent markers into one span.
+    fn push_marker(&mut self, marker: char) {
+        match self.spans.last_mut() {
+            Some(span) if self.after_marker => span.push(marker),
+            _ => self.push_span(marker.to_string()),
+        }
+        self.after_marker = true;
+    }
+
+    /// Translate the text of `line`, keeping leading separators such as
+    /// bullets and trailing whitespace; templates without letters are not
+    /// translated.
+    fn translate(
+        &self,
+        line: &str,
+        translator: &dyn Translator,
+        failures: &mut Vec<io::Error>,
+    ) -> String {
+        let untranslated =
+            || restore(&self.text, &self.spans, true).unwrap_or_else(|| line.to_string());
+        let text = unescape_telegram_markdown(&self.text);
+        if !text.chars().any(char::is_alphabetic) {
+            return untranslated();
+        }
+        let start = text
+            .find(|c: char| c.is_alphanumeric() || TEXT_OPENERS.contains(c))
+            .unwrap_or(0);
+        let end = text.trim_end().len();
+        let translated = translator
+            .translate(&text[start..end])
+            .and_then(|translated| {
+                restore(&translated, &self.spans, false).ok_or_else(|| {
+                    io::Error::other(format!(
+                        "translation of {:?} lost its placeholders",
+                        &text[start..end]
+                    ))
+                })
+            });
+        match translated {
+            Ok(body) => format!("{}{body}{}", escape(&text[..start]), &text[end..]),
+            Err(err) => {
+                failures.push(err);
+                untranslated()
+            }
+        }
+    }
+}
+
+/// Replace the `{n}` placeholders of `text` with `spans`.
+///
+/// Escaped `text` is copied as written, plain `text` is escaped.
+///
+/// # Returns
+/// `None` unless every span is used exactly once.
+fn restore(text: &str, spans: &[String], escaped: bool) -> Option<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut used = vec![false; spans.len()];
+    let mut piece = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let len = c.len_utf8();
+        if escaped && c == '\\' {
+            let next = rest[len..].chars().next().map_or(0, char::len_utf8);
+            piece.push_str(&rest[..len + next]);
+            rest = &rest[len + next..];
+            continue;
+        }
+        let placeholder = (c == '{')
+            .then(|| rest[1..].split_once('}'))
+            .flatten()
+            .and_then(|(digits, _)| digits.parse::<usize>().ok().map(|n| (n, digits.len())))
+            .filter(|&(n, _)| n < spans.len());
+        let Some((index, digits)) = placeholder else {
+            piece.push(c);
+            rest = &rest[len..];
+            continue;
+        };
+        if std::mem::replace(&mut used[index], true) {
+            return None;
+        }
+        out.push_str(&if escaped {
+            piece.clone()
+        } else {
+            escape(&piece)
+        });
+        out.push_str(&spans[index]);
+        piece.clear();
+        rest = &rest[digits + 2..];
+    }
+    out.push_str(&if escaped { piece } else { escape(&piece) });
+    used.into_iter().all(|used| used).then_some(out)
+}
+
+/// Find the first unescaped occurrence of `target` in `text`.
+fn find_unescaped(text: &str, target: char) -> Option<usize> {
+    let mut escaped = false;
+    for (idx, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == target {
+            return Some(idx);
+        }
+    }
+    None
+}
+
+/// Translate the text of a rendered Telegram Markdown line.
+///
+/// The line is translated as a whole with placeholders standing for links,
+/// inline code, terms of `glossary` and formatting markers, which are kept as
+/// written; link texts are translated on their own. When the translation
+/// drops or repeats a placeholder, the line is kept as written.
+///
+/// # Returns
+/// The translated line and the errors of texts left untranslated.
+pub fn translate_line(
+    line: &str,
+    translator: &dyn Translator,
+    glossary: &Glossary,
+) -> (String, Vec<io::Error>) {
+    translate_escaped(line, translator, &glossary.escaped())
+}
+
+/// [`translate_line`] with `glossary` already escaped.
+///
+/// A leading block quote `>` is kept as written.
+fn translate_escaped(
+    line: &str,
+    translator: &dyn Translator,
+    glossary: &Glossary,
+) -> (String, Vec<io::Error>) {
+    if let Some(quoted) = line.strip_prefix('>') {
+        let (translated, failures) = translate_escaped(quoted, translator, glossary);
+        return (format!(">{translated}"), failures);
+    }
+    let mut failures = Vec::new();
+    let mut template = Template::default();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let len = c.len_utf8();
+        if let Some(term) = glossary.term_at(line, line.len() - rest.len()) {
+            template.push_span(rest[..term].to_string());
+            rest = &rest[term..];
+            continue;
+        }
+        match c {
+            '\\' => {
+                let next = rest[len..].chars().next().map_or(0, char::len_utf8);
+                template.push_text(&rest[..len + next]);
+                rest = &rest[len + next..];
+            }
+            '`' => {
+                let end = find_unescaped(&rest[1..], '`').map_or(rest.len(), |idx| idx + 2);
+                template.push_span(rest[..end].to_string());
+                rest = &rest[end..];
+            }
+            '[' => {
+                let link = find_unescaped(&rest[1..], ']')
+                    .filter(|&close| rest[1 + close + 1..].starts_with('('))
+                    .and_then(|close| {
+                        let url_start = close + 3;
+                        find_unescaped(&rest[url_start..], ')')
+                            .map(|end| (close + 1, url_start + end + 1))
+                    });
+                match link {
+                    Some((close, end)) => {
+                        let (text, errors) =
+                            translate_escaped(&rest[1..close], translator, glossary);
+                        failures.extend(errors);
+                        template.push_span(format!("[{text}{}", &rest[close..end]));
+                        rest = &rest[end..];
+                    }
+                    None => {
+                        template.push_span("[".to_string());
+                        rest = &rest[1..];
+                    }
+                }
+            }
+            '*' | '_' | '~' | '|' => {
+                template.push_marker(c);
+                rest = &rest[1..];
+            }
+            _ => {
+                template.push_text(&rest[..len]);
+                rest = &rest[len..];
+            }
+        }
+    }
+    let out = template.translate(line, translator, &mut failures);
+    (out, failures)
+}
+
+/// Translate every content line of `section` except subheadings and code
+/// blocks; text that cannot be translated is kept as written.
+///
+/// # Returns
+/// The errors of texts left untranslated.
+pub(crate) fn translate_section(
+    section: &mut Section,
+    translator: &dyn Translator,
+    glossary: &Glossary,
+) -> Vec<io::Error> {
+    let glossary = glossary.escaped();
+    let mut failures = Vec::new();
+    for (index, line) in section.lines.iter_mut().enumerate() {
+        if line.starts_with("```") || section.subheadings.iter().any(|sub| sub.line == index) {
+            continue;
+        }
+        let (translated, errors) = translate_escaped(line, translator, &glossary);
+        *line = translated;
+        failures.extend(errors);
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Upper;
+
+    impl Translator for Upper {
+        fn translate(&self, text: &str) -> io::Result<String> {
+            Ok(text.to_uppercase())
+        }
+    }
+
+    #[test]
+    fn keeps_urls_code_and_markup() {
+        let line = "• 🎧 [Fast builds\\. Really](https://example.com/a_\\(b\\)) with `cargo build` and **bold** text\\!";
+        let glossary = Glossary {
+            terms: vec!["cargo build".to_string(), "Really".to_string()],
+        };
+        let (translated, errors) = translate_line(line, &Upper, &glossary);
+        assert!(errors.is_empty());
+        assert_eq!(
+            translated,
+            "• 🎧 [FAST BUILDS\\. Really](https://example.com/a_\\(b\\)) WITH `cargo build` AND **BOLD** TEXT\\!"
+        );
+    }
+
+    #[test]
+    fn keeps_block_quote_marker() {
+        let (translated, errors) = translate_line(">First line\\.", &Upper, &Glossary::default());
+        assert!(errors.is_empty());
+        assert_eq!(translated, ">FIRST LINE\\.");
+    }
+
+    struct Reorder;
+
+    impl Translator for Reorder {
+        fn translate(&self, text: &str) -> io::Result<String> {
+            Ok(match text {
+                "Read {0} by {1}" => "{1}: {0}".to_string(),
+                "Read {0} twice" => "{0} {0}".to_string(),
+                other => other.to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn translates_whole_lines_with_placeholders() {
+        let glossary = Glossary {
+            terms: vec!["ansic".to_string()],
+        };
+        let (translated, errors) = translate_line(
+            "• Read [post](https://p.example) by ansic",
+            &Reorder,
+            &glossary,
+        );
+        assert!(errors.is_empty());
+        assert_eq!(translated, "• ansic: [post](https://p.example)");
+
+        let (translated, errors) =
+            translate_line("Read `code` twice", &Reorder, &Glossary::default());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(translated, "Read `code` twice");
+    }
+
+    #[test]
+    fn memory_without_backend_only_uses_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ru.json");
+        fs::write(&path, r#"{"Hello {0}": "Привет {0}"}"#).unwrap();
+        let memory = TranslationMemory::open(&path, None).unwrap();
+        let (translated, errors) = translate_line(
+            "Hello [World](https://w.example)",
+            &memory,
+            &Glossary::default(),
+        );
+        assert_eq!(translated, "Привет [World](https://w.example)");
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn memory_stores_backend_translations() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ru.json");
+        let memory = TranslationMemory::open(&path, Some(Box::new(Upper))).unwrap();
+        assert_eq!(memory.translate("hi").unwrap(), "HI");
+        memory.flush().unwrap();
+        let reopened = TranslationMemory::open(&path, None).unwrap();
+        assert_eq!(reopened.translate("hi").unwrap(), "HI");
+    }
+}
@@ -0,0 +1,232 @@
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+
+use crate::shared::links::is_tracking_param;
+
+/// Host replacements applied with `canonical_hosts = true`.
+const KNOWN_HOSTS: &[(&str, &str)] = &[
+    ("reddit.com", "old.reddit.com"),
+    ("www.reddit.com", "old.reddit.com"),
+    ("mobile.twitter.com", "twitter.com"),
+    ("m.youtube.com", "www.youtube.com"),
+    ("m.wikipedia.org", "wikipedia.org"),
+];
+
+/// A user-provided rewrite applied to every link.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RewriteRule {
+    /// Regular expression matched against the whole URL.
+    #[serde(deserialize_with = "deserialize_regex")]
+    pub pattern: Regex,
+    /// Replacement text; `$1` or `${name}` refer to capture groups.
+    pub replace: String,
+}
+
+fn deserialize_regex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Regex, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+    Regex::new(&pattern).map_err(serde::de::Error::custom)
+}
+
+/// How links are rewritten while the issue is parsed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UrlOptions {
+    /// Remove `utm_*` and other tracking query parameters.
+    pub strip_tracking: bool,
+    /// Apply the built-in host replacements, e.g. `old.reddit.com`, and turn
+    /// LWN subscriber links into article links.
+    pub canonical_hosts: bool,
+    /// Additional host replacements.
+    pub hosts: BTreeMap<String, String>,
+    /// Regex rewrites applied in order after the other steps.
+    pub rules: Vec<RewriteRule>,
+}
+
+impl Default for UrlOptions {
+    fn default() -> Self {
+        Self {
+            strip_tracking: true,
+            canonical_hosts: false,
+            hosts: BTreeMap::new(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// A URL split into the parts rewritten separately.
+struct UrlParts<'a> {
+    scheme: &'a str,
+    host: &'a str,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> UrlParts<'a> {
+    fn split(url: &'a str) -> Option<Self> {
+        let (scheme, rest) = url.split_once("://")?;
+        let (rest, fragment) = match rest.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (rest, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (host, path) = rest.find('/').map_or((rest, ""), |idx| rest.split_at(idx));
+        Some(Self {
+            scheme,
+            host,
+            path,
+            query,
+            fragment,
+        })
+    }
+
+    fn join(&self, host: &str, path: &str, query: &str) -> String {
+        let mut url = format!("{}://{host}{path}", self.scheme);
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(query);
+        }
+        if let Some(fragment) = self.fragment {
+            url.push('#');
+            url.push_str(fragment);
+        }
+        url
+    }
+}
+
+/// Return `true` for `medium.com` and its subdomains.
+fn is_medium(host: &str) -> bool {
+    host == "medium.com" || host.ends_with(".medium.com")
+}
+
+/// Turn `/SubscriberLink/1034567/abc123/` into `/Articles/1034567/`.
+fn lwn_article(path: &str) -> Option<String> {
+    let rest = path.strip_prefix("/SubscriberLink/")?;
+    let id = rest.split('/').next()?;
+    (!id.is_empty() && id.chars().all(|c| c.is_ascii_digit())).then(|| format!("/Articles/{id}/"))
+}
+
+impl UrlOptions {
+    /// Rewrite `url` according to these options.
+    ///
+    /// # Returns
+    /// The rewritten URL; URLs without a scheme only go through the rules.
+    pub fn rewrite(&self, url: &str) -> String {
+        let mut url = match UrlParts::split(url) {
+            Some(parts) => self.rewrite_parts(&parts),
+            None => url.to_string(),
+        };
+        for rule in &self.rules {
+            url = rule
+                .pattern
+                .replace(&url, rule.replace.as_str())
+                .into_owned();
+        }
+        url
+    }
+
+    fn rewrite_parts(&self, parts: &UrlParts<'_>) -> String {
+        let host_lower = parts.host.to_ascii_lowercase();
+        let mut host = parts.host.to_string();
+        let mut path = parts.path.to_string();
+        if self.canonical_hosts {
+            if let Some((_, to)) = KNOWN_HOSTS.iter().find(|(from, _)| *from == host_lower) {
+                host = to.to_string();
+            }
+            if host_lower == "lwn.net"
+                && let Some(article) = lwn_article(parts.path)
+            {
+                path = article;
+            }
+        }
+        if let Some((_, to)) = self
+            .hosts
+            .iter()
+            .find(|(from, _)| from.eq_ignore_ascii_case(&host_lower))
+        {
+            host = to.clone();
+        }
+
+        let query = parts.query.unwrap_or_default();
+        let query = if self.strip_tracking {
+            query
+                .split('&')
+                .filter(|pair| {
+                    let name = pair.split('=').next().unwrap_or_default();
+                    !is_tracking_param(name) && !(is_medium(&host_lower) && name == "source")
+                })
+                .collect::<Vec<_>>()
+                .join("&")
+        } else {
+            query.to_string()
+        };
+        parts.join(&host, &path, &query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_tracking_parameters_by_default() {
+        let options = UrlOptions::default();
+        assert_eq!(
+            options.rewrite("https://example.com/a?utm_source=twir&id=1&fbclid=x#top"),
+            "https://example.com/a?id=1#top"
+        );
+        assert_eq!(
+            options.rewrite("https://blog.medium.com/post-1a2b?source=rss----1"),
+            "https://blog.medium.com/post-1a2b"
+        );
+        assert_eq!(
+            options.rewrite("https://example.com/?source=x"),
+            "https://example.com/?source=x"
+        );
+    }
+
+    #[test]
+    fn canonicalises_known_hosts_when_enabled() {
+        let options = UrlOptions {
+            canonical_hosts: true,
+            ..UrlOptions::default()
+        };
+        assert_eq!(
+            options.rewrite("https://www.reddit.com/r/rust/comments/1"),
+            "https://old.reddit.com/r/rust/comments/1"
+        );
+        assert_eq!(
+            options.rewrite("https://lwn.net/SubscriberLink/1034567/5d6e7f/"),
+            "https://lwn.net/Articles/1034567/"
+        );
+        assert_eq!(
+            UrlOptions::default().rewrite("https://www.reddit.com/r/rust"),
+            "https://www.reddit.com/r/rust"
+        );
+    }
+
+    #[test]
+    fn applies_hosts_and_rules_from_config() {
+        let options: UrlOptions = toml::from_str(
+            r#"
+hosts = { "x.com" = "twitter.com" }
+rules = [{ pattern = '^https://docs\.rs/([^/]+)$', replace = "https://docs.rs/$1/latest/" }]
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            options.rewrite("https://x.com/rustlang"),
+            "https://twitter.com/rustlang"
+        );
+        assert_eq!(
+            options.rewrite("https://docs.rs/serde"),
+            "https://docs.rs/serde/latest/"
+        );
+        assert!(toml::from_str::<UrlOptions>("rules = [{ pattern = '(', replace = '' }]").is_err());
+    }
+}
@@ -35,6 +35,7 @@ pub fn validate_telegram_markdown(text: &str) -> Result<(), MarkdownError> {
     let chars: Vec<char> = text.chars().collect();
     let mut stack: VecDeque<&str> = VecDeque::new();
     let mut in_code_block = false;
+    let mut in_inline_code = false;
     if let Some(&first) = chars.first()
         && matches!(first, '-' | '>' | '#' | '+' | '=' | '{' | '}' | '.' | '!')
     {
@@ -49,6 +50,11 @@ pub fn validate_telegram_markdown(text: &str) -> Result<(), MarkdownError> {
             i += 1;
             continue;
         }
+        if in_inline_code && ch != '`' {
+            // Inside inline code only backslashes and backticks are escaped.
+            i += if ch == '\\' { 2 } else { 1 };
+            continue;
+        }
         match ch {
             '*' => {
                 let token = if i + 1 < chars.len() && chars[i + 1] == '*' {
@@ -88,6 +94,7 @@ pub fn validate_telegram_markdown(text: &str) -> Result<(), MarkdownError> {
                     in_code_block = !in_code_block;
                     "```"
                 } else {
+                    in_inline_code = !in_inline_code;
                     "`"
                 };
                 toggle_token(token, &mut stack)?;
@@ -139,6 +146,11 @@ pub fn validate_telegram_markdown(text: &str) -> Result<(), MarkdownError> {
                 }
                 let prev = if i == 0 { None } else { Some(chars[i - 1]) };
                 let next = chars.get(i + 1).copied();
+                // `>` opening a line starts a block quote.
+                if ch == '>' && prev == Some('\n') {
+                    i += 1;
+                    continue;
+                }
                 if prev != Some('\\')
                     && !(prev.map(|c| c.is_ascii_alphanumeric()).unwrap_or(false)
                         && next.map(|c| c.is_ascii_alphanumeric()).unwrap_or(false))
@@ -199,6 +211,18 @@ mod tests {
         assert!(validate_telegram_markdown("some - text").is_err());
     }
 
+    #[test]
+    fn inline_code_needs_no_escapes() {
+        assert!(validate_telegram_markdown("use `Result<(), E>` and `#[must_use]` \\- ok").is_ok());
+        assert!(validate_telegram_markdown("`a\\`b` then - text").is_err());
+    }
+
+    #[test]
+    fn block_quote_lines_start_with_gt() {
+        assert!(validate_telegram_markdown("Quote:\n>It works\\.\n>\n— Ferris").is_ok());
+        assert!(validate_telegram_markdown("Quote: a > b").is_err());
+    }
+
     #[test]
     fn accepts_escaped_dash() {
         assert!(validate_telegram_markdown("some \\- text").is_ok());
@@ -1,5 +1,78 @@
+#![allow(dead_code)]
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+};
+
 use twir_deploy_notify::validator::validate_telegram_markdown;
 
+/// Font committed under `tests/fonts` for the rendered image cards.
+pub const FONT: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fonts/DejaVuSansMono.ttf"
+);
+
 pub fn assert_valid_markdown(post: &str) {
     validate_telegram_markdown(post).unwrap_or_else(|e| panic!("invalid telegram markdown: {e}"));
 }
+
+/// An HTTP request received by [`serve`].
+pub struct Request {
+    /// Request line such as `POST /botTOKEN/sendMessage HTTP/1.1`.
+    pub line: String,
+    pub body: String,
+}
+
+/// Answer `count` HTTP requests on a local port.
+///
+/// `respond` receives the index and contents of each request and returns
+/// the status line and the JSON body of the reply.
+///
+/// # Returns
+/// The base URL of the server and a handle yielding the received requests.
+pub fn serve<F>(count: usize, respond: F) -> (String, thread::JoinHandle<Vec<Request>>)
+where
+    F: Fn(usize, &Request) -> (&'static str, String) + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        (0..count)
+            .map(|index| {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = header.to_ascii_lowercase().strip_prefix("content-length:")
+                    {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let request = Request {
+                    line: line.trim_end().to_string(),
+                    body: String::from_utf8_lossy(&body).into_owned(),
+                };
+                let (status, reply) = respond(index, &request);
+                write!(
+                    &stream,
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{reply}",
+                    reply.len()
+                )
+                .unwrap();
+                request
+            })
+            .collect()
+    });
+    (base, handle)
+}
@@ -0,0 +1,50 @@
+use twir_deploy_notify::{generator, validator::validate_telegram_markdown};
+
+mod common;
+
+use common::FONT;
+
+#[test]
+fn cover_caption_lists_issue_and_sections() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md");
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("artwork.jpg");
+    std::fs::write(&image, b"\xff\xd8\xff fake").unwrap();
+    let options =
+        generator::parse_options(&format!("[cover]\nimage = \"{}\"\n", image.display())).unwrap();
+    let issue = generator::generate_issue(input.to_string(), &options).unwrap();
+    let cover = generator::generate_cover(input, &issue, &options)
+        .unwrap()
+        .unwrap();
+    assert_eq!(cover.file_name, "artwork.jpg");
+    assert_eq!(cover.image, b"\xff\xd8\xff fake");
+    assert!(
+        cover
+            .caption
+            .starts_with("**This Week in Rust 606**\n\\#606 — 2025\\-07\\-02\n\n• ")
+    );
+    assert!(cover.caption.contains("\n• Crate of the Week"));
+    assert!(cover.caption.chars().count() <= generator::TELEGRAM_CAPTION_LIMIT);
+    validate_telegram_markdown(&cover.caption).unwrap();
+    assert!(
+        generator::generate_cover(input, &issue, &generator::GenerationOptions::default())
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn cover_card_is_rendered_from_metadata() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md");
+    let options =
+        generator::parse_options(&format!("[cover.card]\nfont = \"{FONT}\"\nwidth = 640\n"))
+            .unwrap();
+    let issue = generator::generate_issue(input.to_string(), &options).unwrap();
+    let cover = generator::generate_cover(input, &issue, &options)
+        .unwrap()
+        .unwrap();
+    assert_eq!(cover.file_name, generator::COVER_FILE);
+    assert!(cover.image.starts_with(b"\x89PNG\r\n\x1a\n"));
+    assert_eq!(&cover.image[16..20], 640u32.to_be_bytes());
+    assert!(u32::from_be_bytes(cover.image[20..24].try_into().unwrap()) >= 360);
+}
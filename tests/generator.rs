@@ -103,6 +103,32 @@ proptest! {
     }
 }
 
+fn arb_linked_paragraph() -> impl Strategy<Value = String> {
+    let word = || proptest::string::string_regex("[A-Za-z0-9]{1,12}").unwrap();
+    let link = (
+        word(),
+        proptest::string::string_regex("[a-z0-9]{1,20}").unwrap(),
+    )
+        .prop_map(|(text, path)| format!("[{text} {text}](https://example.com/{path})"));
+    let bold = word().prop_map(|text| format!("*{text} {text}*"));
+    let escaped = word().prop_map(|text| format!("{text}\\."));
+    prop::collection::vec(prop_oneof![link, bold, escaped], 600..900)
+        .prop_map(|parts| parts.join(" "))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+    #[test]
+    fn long_lines_keep_entities_intact(line in arb_linked_paragraph()) {
+        let posts = split_posts(&line, TELEGRAM_LIMIT);
+        prop_assert!(posts.len() >= 2);
+        for p in posts {
+            prop_assert!(p.len() <= TELEGRAM_LIMIT);
+            common::assert_valid_markdown(&p);
+        }
+    }
+}
+
 #[test]
 fn boundary_escape_preserved() {
     let mut input = "a".repeat(TELEGRAM_LIMIT - 1);
@@ -129,8 +155,11 @@ fn quote_section_spacing() {
     let posts =
         generator::generate_posts(include_str!("2025-07-02-this-week-in-rust.md").to_string())
             .unwrap();
-    assert!(posts.len() >= 5);
-    let lines: Vec<_> = posts[4].lines().collect();
+    let post = posts
+        .iter()
+        .find(|p| p.contains("Quote of the Week"))
+        .expect("quote post present");
+    let lines: Vec<_> = post.lines().collect();
     let idx = lines
         .iter()
         .position(|l| l.contains("Quote of the Week"))
@@ -238,3 +267,434 @@ fn latest_issue_call_for_testing_is_formatted() {
         "• No calls for testing were issued this week by [Rust](https://github.com/rust-lang/rust/labels/call-for-testing)"
     ));
 }
+
+#[test]
+fn balanced_packing_evens_out_posts() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md").to_string();
+    let greedy = generate_posts(input.clone()).unwrap();
+    let options = generator::GenerationOptions {
+        packing: generator::PackingStrategy::Balanced,
+        ..Default::default()
+    };
+    let balanced = generator::generate_posts_with_options(input, &options).unwrap();
+    assert!(balanced.len() <= greedy.len());
+
+    let spread = |posts: &[String]| {
+        let sizes: Vec<usize> = posts.iter().map(String::len).collect();
+        sizes.iter().max().unwrap() - sizes.iter().min().unwrap()
+    };
+    assert!(spread(&balanced) < spread(&greedy));
+
+    let last = balanced.last().unwrap();
+    assert!(last.contains("View web version"));
+    assert!(last.contains("Quote of the Week"));
+    for post in &balanced {
+        common::assert_valid_markdown(post);
+    }
+}
+
+#[test]
+fn config_selects_and_orders_sections() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md").to_string();
+    let options = generator::parse_options(
+        r#"
+[sections]
+include = ["Quote of the Week", "Crate of the Week", "Official"]
+order = ["Crate of the Week", "Official", "Quote of the Week"]
+"#,
+    )
+    .unwrap();
+    let posts = generator::generate_posts_with_options(input, &options).unwrap();
+    let text = posts.join("\n");
+
+    let crate_pos = text.find("CRATE OF THE WEEK").expect("crate of the week");
+    let official_pos = text.find("OFFICIAL").expect("official");
+    let quote_pos = text.find("QUOTE OF THE WEEK").expect("quote of the week");
+    assert!(crate_pos < official_pos && official_pos < quote_pos);
+    assert!(!text.contains("Newsletters"));
+    assert!(!text.contains("Upcoming Events"));
+    assert!(text.contains("View web version"));
+    for post in &posts {
+        twir_deploy_notify::validator::validate_telegram_markdown(post).unwrap();
+    }
+}
+
+#[test]
+fn event_digest_is_published_as_extra_post() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md").to_string();
+    let plain = generate_posts(input.clone()).unwrap();
+    let options = generator::parse_options(
+        r#"
+[events]
+regions = ["Oceania"]
+cities = ["Berlin"]
+standalone = true
+"#,
+    )
+    .unwrap();
+    let posts = generator::generate_posts_with_options(input, &options).unwrap();
+    assert_eq!(posts.len(), plain.len() + 1);
+
+    let digest = posts.last().unwrap();
+    assert!(digest.contains("UPCOMING EVENTS"));
+    assert!(digest.contains("**Oceania**"));
+    assert!(digest.contains("Rust Hack and Learn"));
+    assert!(!digest.contains("Rust Paris"));
+    assert!(posts[posts.len() - 2].contains("View web version"));
+    for post in &posts {
+        twir_deploy_notify::validator::validate_telegram_markdown(post).unwrap();
+    }
+}
+
+#[test]
+fn event_digest_can_form_a_separate_target() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md").to_string();
+    let options = generator::parse_options(
+        r#"
+[sections]
+include = ["Upcoming Events"]
+
+[events]
+regions = ["Europe"]
+"#,
+    )
+    .unwrap();
+    let posts = generator::generate_posts_with_options(input, &options).unwrap();
+    assert_eq!(posts.len(), 1);
+    assert!(posts[0].contains("11\\-02–11\\-04 Florence, IT: [Rustlab 2025]"));
+    assert!(!posts[0].contains("Dallas"));
+    assert!(posts[0].contains("View web version"));
+}
+
+#[test]
+fn event_digest_respects_section_policy() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md").to_string();
+    for sections in [r#"include = ["Jobs"]"#, r#"exclude = ["Upcoming Events"]"#] {
+        let options = generator::parse_options(&format!(
+            "[sections]\n{sections}\n\n[events]\nregions = [\"Europe\"]\n"
+        ))
+        .unwrap();
+        let posts = generator::generate_posts_with_options(input.clone(), &options).unwrap();
+        let text = posts.join("\n");
+        assert!(!text.contains("UPCOMING EVENTS"), "{sections}");
+        assert!(!text.contains("Rustlab 2025"), "{sections}");
+    }
+}
+
+#[test]
+fn calendar_exports_upcoming_events() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md");
+    let calendar =
+        generator::generate_calendar(input, &generator::GenerationOptions::default()).unwrap();
+    assert!(calendar.contains("X-WR-CALNAME:This Week in Rust #622 events\r\n"));
+    assert!(calendar.contains("DTSTART;VALUE=DATE:20251102\r\nDTEND;VALUE=DATE:20251105\r\n"));
+    assert!(calendar.contains("CATEGORIES:Virtual,North America,Hybrid\r\n"));
+    assert_eq!(
+        calendar.matches("BEGIN:VEVENT").count(),
+        calendar.matches("END:VEVENT").count()
+    );
+
+    let options = generator::parse_options("[events]\nregions = [\"Oceania\"]\n").unwrap();
+    let oceania = generator::generate_calendar(input, &options).unwrap();
+    assert_eq!(oceania.matches("BEGIN:VEVENT").count(), 2);
+}
+
+#[test]
+fn fcp_can_be_published_as_standalone_post() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md").to_string();
+    let options = generator::parse_options("[fcp]\nstandalone = true\n").unwrap();
+    let posts = generator::generate_posts_with_options(input, &options).unwrap();
+
+    let fcp = posts.last().unwrap();
+    assert!(fcp.contains("FINAL COMMENT PERIOD"));
+    assert!(fcp.contains("What's being decided this week"));
+    assert!(fcp.contains("**[Leadership Council]("));
+    assert!(fcp.contains("❔ _unspecified_ [\\[std\\]\\[BTree\\]"));
+    assert!(fcp.contains("_No new items this week:_ [Rust RFCs](https://github.com/rust-lang/rfcs/labels/final-comment-period), [Cargo]("));
+    let issue = &posts[..posts.len() - 1];
+    assert!(issue.iter().all(|post| !post.contains("NEON fp16")));
+    assert!(
+        issue
+            .iter()
+            .any(|post| post.contains("New and Updated RFCs"))
+    );
+    assert!(issue.last().unwrap().contains("View web version"));
+}
+
+#[test]
+fn standalone_fcp_respects_section_policy() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md").to_string();
+    let options = generator::parse_options(
+        "[sections]\ninclude = [\"Crate of the Week\"]\n\n[fcp]\nstandalone = true\n",
+    )
+    .unwrap();
+    let posts = generator::generate_posts_with_options(input.clone(), &options).unwrap();
+    assert!(
+        posts
+            .iter()
+            .all(|post| !post.contains("FINAL COMMENT PERIOD"))
+    );
+
+    let options = generator::parse_options(
+        "[sections]\ninclude = [\"Updates from the Rust Project\"]\n\n[fcp]\nstandalone = true\n",
+    )
+    .unwrap();
+    let posts = generator::generate_posts_with_options(input, &options).unwrap();
+    assert!(posts.last().unwrap().contains("FINAL COMMENT PERIOD"));
+}
+
+#[test]
+fn long_pull_request_categories_collapse() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md").to_string();
+    let options =
+        generator::parse_options("[pulls]\nmax_items = 10\ncollapse = [\"cargo\"]\n").unwrap();
+    let text = generator::generate_posts_with_options(input, &options)
+        .unwrap()
+        .join("\n");
+    let web = "(https://this-week-in-rust.org/blog/2025/10/22/this-week-in-rust-622/)";
+    assert!(text.contains(&format!("14 pull requests — [see web version]{web}")));
+    assert!(text.contains(&format!("3 pull requests — [see web version]{web}")));
+    assert!(!text.contains("rip Chalk out of the codebase"));
+    assert!(text.contains("bitset cleanups"));
+}
+
+#[test]
+fn perf_triage_renders_as_summary_card() {
+    let input = include_str!("2025-06-25-this-week-in-rust.md").to_string();
+    let text = generator::generate_posts(input).unwrap().join("\n");
+    assert!(text.contains("📉 2 regressions · 📈 4 improvements · 🔀 10 mixed \\(7 in rollups\\)"));
+    assert!(text.contains("📊 mean 1\\.0%, range \\[\\-7\\.3%, 9\\.1%\\]"));
+    assert!(text.contains("· by [rylev](https://github.com/rylev) · [full report]("));
+    assert!(!text.contains("| (instructions:u)"));
+}
+
+#[test]
+fn crate_of_week_is_enriched_from_local_index() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md").to_string();
+    let index = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/crates-index");
+    let options = generator::parse_options(&format!("[crates]\nindex = {index:?}\n")).unwrap();
+    let text = generator::generate_posts_with_options(input, &options)
+        .unwrap()
+        .join("\n");
+    assert!(text.contains(
+        "crates.io/crates/ansic), a proc macro providing a DSL to output ANSI escape strings with zero runtime overhead\\.\n📦 ansic 0\\.1\\.4 · MIT · A proc macro DSL"
+    ));
+}
+
+#[test]
+fn links_are_rewritten_while_parsing() {
+    let input = "Title: T\nNumber: 1\nDate: 2025-01-01\n\n## News\n\n* [Post](https://www.reddit.com/r/rust/1?utm_source=twir&x=1)\n* [Docs](https://docs.rs/serde)\n".to_string();
+    let options = generator::parse_options(
+        "[urls]\ncanonical_hosts = true\nrules = [{ pattern = '^https://docs\\.rs/(\\w+)$', replace = 'https://docs.rs/$1/latest/' }]\n",
+    )
+    .unwrap();
+    let text = generator::generate_posts_with_options(input.clone(), &options)
+        .unwrap()
+        .join("\n");
+    assert!(text.contains("[Post](https://old.reddit.com/r/rust/1?x=1)"));
+    assert!(text.contains("[Docs](https://docs.rs/serde/latest/)"));
+
+    let text = generator::generate_posts(input).unwrap().join("\n");
+    assert!(text.contains("[Post](https://www.reddit.com/r/rust/1?x=1)"));
+}
+
+#[test]
+fn media_tags_can_filter_items() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md").to_string();
+    let options = generator::parse_options("[tags]\nexclude = [\"audio\"]\n").unwrap();
+    let text = generator::generate_posts_with_options(input, &options)
+        .unwrap()
+        .join("\n");
+    assert!(text.contains("• 🎬 [Top 10 Games from Bevy Jam 6]("));
+    assert!(!text.contains("Dioxus with Jonathan Kelley"));
+    assert!(!text.contains("🎧"));
+}
+
+#[test]
+fn theme_restyles_headings() {
+    let input = "Title: T\nNumber: 1\nDate: 2025-01-01\n\n## Crate of the Week\n\n### Compiler\n\n* [a](https://a.example)\n".to_string();
+    let options = generator::parse_options(
+        "[theme]\ncase = \"title\"\nstyle = \"underline\"\nseparator = \"───\"\nsubheadings = { compiler = \"⚙️\" }\n",
+    )
+    .unwrap();
+    let text = generator::generate_posts_with_options(input, &options)
+        .unwrap()
+        .join("\n");
+    assert!(text.contains("📦 __Crate Of The Week__ 📦\n───\n"));
+    assert!(text.contains("__Compiler:__ ⚙️"));
+}
+
+#[test]
+fn russian_locale_translates_generated_text() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md").to_string();
+    let options = generator::parse_options("locale = \"ru\"\n").unwrap();
+    let posts = generator::generate_posts_with_options(input, &options).unwrap();
+    let text = posts.join("\n");
+    assert!(text.contains("**КРЕЙТ НЕДЕЛИ**"));
+    assert!(text.contains("**Официально**\n"));
+    assert!(text.contains("**Цитата недели:** 💬"));
+    assert!(text.contains("[Веб\\-версия выпуска]("));
+    assert!(posts[1].starts_with("*Часть 2/"));
+    assert!(!text.contains("View web version"));
+}
+
+#[test]
+fn custom_transforms_run_with_builtins() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md").to_string();
+    let options = generator::parse_options("[transforms]\ndisable = [\"jobs\"]\n").unwrap();
+    let mut transforms = generator::TransformRegistry::with_builtins();
+    transforms
+        .register(
+            "crate-footer",
+            "crate of the week",
+            50,
+            |section: &mut twir_deploy_notify::parser::Section, _: &generator::Messages| {
+                section.lines.push("🦀 Suggest a crate\\!".to_string());
+            },
+        )
+        .unwrap();
+    let text = generator::generate_posts_with_transforms(input, &options, &transforms, None)
+        .unwrap()
+        .join("\n");
+    assert!(text.contains("🦀 Suggest a crate\\!"));
+    assert!(!text.contains("Rust Jobs chat"));
+    assert!(text.contains("💬"));
+}
+
+#[test]
+fn config_rules_rewrite_sections() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md").to_string();
+    let options = generator::parse_options(
+        r#"
+[[rules]]
+section = "crate of the week"
+line = "^Thanks to"
+action = "drop"
+
+[[rules]]
+section = "crate of the week"
+line = "^This week's crate is"
+action = "move"
+to = "Highlights"
+
+[[rules]]
+line = "proc macro"
+action = "replace"
+with = "procedural macro"
+"#,
+    )
+    .unwrap();
+    let changes = generator::preview_rules(&input, &options);
+    let titles: Vec<_> = changes.iter().map(|c| c.title.as_str()).collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Updates from Rust Community",
+            "Crate of the Week",
+            "Highlights"
+        ]
+    );
+    assert!(changes[1].before.iter().any(|l| l.starts_with("Thanks to")));
+    assert!(changes[2].before.is_empty());
+    assert!(changes[2].after[0].contains("a procedural macro providing"));
+
+    let text = generator::generate_posts_with_options(input, &options)
+        .unwrap()
+        .join("\n");
+    assert!(!text.contains("Thanks to [Zeon]"));
+    assert!(text.contains("HIGHLIGHTS"));
+}
+
+#[test]
+fn jobs_section_uses_configured_resources_and_postings() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md").to_string();
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/jobs");
+    let options = generator::load_options(&dir.join("jobs.toml")).unwrap();
+    let text = generator::generate_posts_with_options(input, &options)
+        .unwrap()
+        .join("\n");
+    assert!(text.contains("🇩🇪 [Rust jobs in Germany](https://t.me/rust_jobs_de)"));
+    assert!(!text.contains("Rust Job Reddit Thread"));
+    assert!(!text.contains("Rust HH jobs"));
+    assert!(text.contains(
+        "• [Ferrous Systems — Full time, Berlin, DE](https://www.reddit.com/r/rust/comments/1llcso7/official_rrust_whos_hiring_thread_for_jobseekers/mzz0002/)"
+    ));
+    assert!(text.contains("Oxide Computer Company — Full time, Emeryville, CA, US"));
+    assert!(!text.contains("game developer"));
+}
+
+#[test]
+fn table_of_contents_links_sections_to_their_posts() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md");
+    let options = generator::parse_options("[toc]\n\n[fcp]\nstandalone = true\n").unwrap();
+    let issue = generator::generate_issue(input.to_string(), &options).unwrap();
+    let toc = generator::generate_toc(&issue, &options);
+    let posts = &issue.posts;
+
+    assert_eq!(toc.first().map(|entry| entry.post), Some(0));
+    assert!(toc.windows(2).all(|pair| pair[0].post <= pair[1].post));
+    for entry in &toc {
+        assert!(posts[entry.post].contains(&entry.title.to_uppercase()));
+    }
+    let fcp = toc.last().unwrap();
+    assert_eq!(fcp.title, "Final Comment Period");
+    assert_eq!(fcp.post, posts.len() - 1);
+    assert!(toc.iter().any(|entry| entry.title == "Crate of the Week"));
+
+    let ids: Vec<i64> = (100..).take(posts.len()).collect();
+    let text = generator::render_toc(&toc, &ids, "-1001234", options.locale.messages()).unwrap();
+    common::assert_valid_markdown(&text);
+    assert!(
+        text.starts_with("**Contents**\n• [Updates from Rust Community](https://t.me/c/1234/100)")
+    );
+    assert!(text.ends_with(&format!(
+        "• [Final Comment Period](https://t.me/c/1234/{})\n",
+        99 + posts.len()
+    )));
+}
+
+#[test]
+fn url_rules_apply_to_fcp_pulls_and_events() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md").to_string();
+    let options = generator::parse_options(
+        "[urls]\nrules = [{ pattern = '^https://github\\.com/(.*)$', replace = 'https://gh.example/$1' }]\n\n[events]\nregions = ['Europe']\n",
+    )
+    .unwrap();
+    let posts = generator::generate_posts_with_options(input, &options).unwrap();
+    let text = posts.join("\n");
+    assert!(!text.contains("https://github.com/"));
+    assert!(text.contains("https://gh.example/rust-lang/"));
+    assert!(text.contains("[simulacrum](https://gh.example/simulacrum)"));
+    assert!(text.contains("\n✅ _merge_ [") || text.contains("❔ _unspecified_ ["));
+}
+
+#[test]
+fn document_export_applies_url_rules() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md");
+    let options = generator::parse_options(
+        "[urls]\nrules = [{ pattern = '^https://github\\.com/(.*)$', replace = 'https://gh.example/$1' }]\n",
+    )
+    .unwrap();
+    let json = serde_json::to_string(&generator::generate_document(input, &options)).unwrap();
+    assert!(!json.contains("https://github.com/"));
+    assert!(json.contains("https://gh.example/rust-lang/"));
+}
+
+#[test]
+fn pull_digest_and_truncation_follow_the_locale() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md").to_string();
+    let options = generator::parse_options(
+        "locale = \"ru\"\n\n[pulls]\nmax_items = 3\n\n[[sections.truncate]]\ntitle = \"Observations/Thoughts\"\nmax_items = 1\n",
+    )
+    .unwrap();
+    let text = generator::generate_posts_with_options(input, &options)
+        .unwrap()
+        .join("\n");
+    assert!(text.contains("Принято PR: 369"));
+    assert!(!text.contains("PRs merged"));
+    assert!(text.contains("PR: "));
+    assert!(text.contains("см\\. веб\\-версию"));
+    assert!(!text.contains("pull requests —"));
+    assert!(text.contains("_…и ещё "));
+    assert!(!text.contains("more_"));
+}
@@ -0,0 +1,49 @@
+use twir_deploy_notify::generator::{self, LinkProblem, Severity};
+
+mod common;
+
+#[test]
+fn fixture_links_have_no_errors() {
+    let input = include_str!("2025-10-22-this-week-in-rust.md").to_string();
+    let posts = generator::generate_posts(input).unwrap();
+    let links = generator::extract_links(&posts);
+    assert!(links.len() > 100);
+    let reports = generator::lint_links(&links);
+    assert!(
+        reports
+            .iter()
+            .all(|report| report.problem.severity() == Severity::Warning),
+        "{reports:?}"
+    );
+    assert!(
+        reports
+            .iter()
+            .any(|report| matches!(report.problem, LinkProblem::Duplicate { .. }))
+    );
+}
+
+#[test]
+fn online_check_uses_local_base() {
+    // Paths containing `missing` are not found.
+    let (base, server) = common::serve(3, |_, request| {
+        let status = if request.line.contains("missing") {
+            "404 Not Found"
+        } else {
+            "200 OK"
+        };
+        (status, String::new())
+    });
+
+    let urls = vec![
+        "https://blog.rust-lang.org/ok".to_string(),
+        "https://example.com/missing".to_string(),
+        "https://example.org/also-ok?x=1".to_string(),
+    ];
+    let statuses = generator::check_links_online(&urls, 2, Some(&base));
+    server.join().unwrap();
+
+    let codes: Vec<_> = statuses.iter().map(|s| s.result.clone()).collect();
+    assert_eq!(codes, vec![Ok(200), Ok(404), Ok(200)]);
+    assert_eq!(statuses[1].url, urls[1]);
+    assert!(!statuses[1].is_ok());
+}
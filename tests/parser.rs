@@ -1,6 +1,7 @@
 use twir_deploy_notify::parser;
 
-use parser::parse_sections;
+use parser::{parse_sections, parse_sections_report};
+use twir_deploy_notify::generator::UrlOptions;
 mod common;
 
 #[test]
@@ -101,3 +102,35 @@ fn compact_summary_table_without_separator_becomes_code_block() {
     );
     assert_eq!(lines[3], "31 artifact comparisons made in total");
 }
+
+#[test]
+fn references_and_raw_html_are_reported() {
+    let input = "## News\n\nSee [the post][post] and [missing][nope] or [video].\n\n[post]: https://example.com/post\n\n<small>\nDiscuss on r/rust\n</small>\n\n<div class=\"ad\">Sponsored</div>\n\nInline <img src=\"x.png\"> and <sub>small print</sub>.\n";
+    let (sections, report) = parse_sections_report(input, &UrlOptions::default());
+    let lines = &sections[0].lines;
+    assert_eq!(
+        lines[0],
+        "See [the post](https://example.com/post) and \\[missing\\]\\[nope\\] or \\[video\\]\\."
+    );
+    assert_eq!(lines[1], "Discuss on r/rust");
+    assert_eq!(lines[2], "Inline  and small print\\.");
+    assert_eq!(report.broken_references, vec!["nope"]);
+    assert_eq!(
+        report.dropped_html,
+        vec!["<div class=\"ad\">Sponsored</div>", "<img src=\"x.png\">"]
+    );
+}
+
+#[test]
+fn inline_code_becomes_code_entity() {
+    let sections = parse_sections(
+        "## Updates\n* Return `Result<(), Uninhabited>` from `#[test]` fns, see @octo\n* [`serde` 2.0](https://example.com/)\n",
+    );
+    assert_eq!(
+        sections[0].lines,
+        vec![
+            "• Return `Result<(), Uninhabited>` from `#[test]` fns, see [octo](https://github.com/octo)",
+            "• [serde 2\\.0](https://example.com/)",
+        ]
+    );
+}
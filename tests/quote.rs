@@ -0,0 +1,94 @@
+use twir_deploy_notify::generator::{self, PhotoPost, Translator};
+
+mod common;
+
+use common::FONT;
+
+#[test]
+fn standalone_quote_is_a_block_quote_post() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md").to_string();
+    let options = generator::parse_options("[quote]\nstandalone = true\n").unwrap();
+    let posts = generator::generate_posts_with_options(input, &options).unwrap();
+    let (quote, issue) = posts.split_last().unwrap();
+    assert!(quote.contains("\n>I love Rust, so I was already biased"));
+    assert!(quote.contains("\n>\n>And the promised benefits"));
+    assert!(quote.trim_end().ends_with(
+        "— [Remo Senekowitsch blogging about their Rust 4 Linux adventure](https://blog.buenzli.dev/rust-for-linux-first-contrib/)"
+    ));
+    let issue = issue.join("\n");
+    assert!(!issue.contains("I love Rust"));
+    assert!(!issue.contains("And the promised benefits"));
+    assert!(issue.contains("This Week in Rust is edited by"));
+}
+
+struct Upper;
+
+impl Translator for Upper {
+    fn translate(&self, text: &str) -> std::io::Result<String> {
+        Ok(text.to_uppercase())
+    }
+}
+
+#[test]
+fn translated_standalone_quote_stays_a_block_quote() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md").to_string();
+    let options = generator::parse_options("[quote]\nstandalone = true\n").unwrap();
+    let posts = generator::generate_posts_with_translator(input, &options, Some(&Upper)).unwrap();
+    let quote = posts.last().unwrap();
+    assert!(quote.contains("\n>I LOVE RUST, SO I WAS ALREADY BIASED"));
+    assert!(quote.contains("\n>\n>AND THE PROMISED BENEFITS"));
+    assert!(!quote.contains("\\>"));
+}
+
+#[test]
+fn quote_card_replaces_the_quote_post() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md");
+    let options = generator::parse_options(&format!(
+        "[quote]\nstandalone = true\n\n[quote.card]\nfont = \"{FONT}\"\nwidth = 640\n"
+    ))
+    .unwrap();
+    let card = generator::generate_quote_card(input, &options)
+        .unwrap()
+        .unwrap();
+    assert!(card.image.starts_with(b"\x89PNG\r\n\x1a\n"));
+    assert_eq!(&card.image[16..20], 640u32.to_be_bytes());
+    assert!(card.caption.contains("QUOTE OF THE WEEK"));
+    assert!(
+        card.caption
+            .ends_with("adventure](https://blog.buenzli.dev/rust-for-linux-first-contrib/)")
+    );
+
+    let posts = generator::generate_posts_with_options(input.to_string(), &options).unwrap();
+    assert!(posts.iter().all(|post| !post.contains("I love Rust")));
+    assert!(
+        generator::generate_quote_card(input, &generator::GenerationOptions::default())
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn quote_card_is_sent_with_send_photo() {
+    let (base, server) = common::serve(1, |_, _| {
+        (
+            "200 OK",
+            r#"{"ok":true,"result":{"message_id":42}}"#.to_string(),
+        )
+    });
+
+    let card = PhotoPost {
+        image: b"\x89PNG fake".to_vec(),
+        file_name: generator::QUOTE_CARD_FILE.to_string(),
+        caption: "*Quote of the Week*".to_string(),
+    };
+    let id =
+        generator::send_photo_to_telegram(&card, &base, "TOKEN", "@channel", true, false, None)
+            .unwrap();
+    assert_eq!(id, 42);
+    let requests = server.join().unwrap();
+    let body = &requests[0].body;
+    assert!(requests[0].line.starts_with("POST /botTOKEN/sendPhoto "));
+    assert!(body.contains("name=\"photo\"; filename=\"quote.png\""));
+    assert!(body.contains("*Quote of the Week*"));
+    assert!(body.contains("MarkdownV2"));
+}
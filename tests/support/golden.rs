@@ -3,7 +3,9 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use twir_deploy_notify::generator::generate_posts;
+use twir_deploy_notify::generator::{
+    GenerationOptions, JSON_FILE, generate_document, generate_posts,
+};
 
 use crate::common::assert_valid_markdown;
 
@@ -39,6 +41,32 @@ pub fn assert_fixture_matches_golden(fixture_name: &str) {
     }
 }
 
+pub fn assert_fixture_matches_json_golden(fixture_name: &str) {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fixture_path = root.join("tests").join(fixture_name);
+    let snapshot_path = root
+        .join("tests")
+        .join("golden")
+        .join(snapshot_name(fixture_name))
+        .join(JSON_FILE);
+
+    let input = fs::read_to_string(&fixture_path)
+        .map(normalize_line_endings)
+        .unwrap_or_else(|e| panic!("failed to read fixture {}: {e}", fixture_path.display()));
+    let actual =
+        serde_json::to_string_pretty(&generate_document(&input, &GenerationOptions::default()))
+            .unwrap_or_else(|e| panic!("failed to serialize document for {fixture_name}: {e}"))
+            + "\n";
+    let expected = fs::read_to_string(&snapshot_path)
+        .map(normalize_line_endings)
+        .unwrap_or_else(|e| panic!("failed to read snapshot {}: {e}", snapshot_path.display()));
+
+    assert_eq!(
+        actual, expected,
+        "JSON snapshot mismatch for {fixture_name}"
+    );
+}
+
 fn snapshot_name(fixture_name: &str) -> &str {
     fixture_name.strip_suffix(".md").unwrap_or(fixture_name)
 }
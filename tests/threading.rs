@@ -0,0 +1,51 @@
+use twir_deploy_notify::generator::{self, ReplyMode, ThreadingOptions};
+
+mod common;
+
+#[test]
+fn parts_reply_to_the_first_post_in_a_topic() {
+    // Telegram assigns consecutive message ids from 100.
+    let (base, server) = common::serve(3, |index, _| {
+        let reply = format!(r#"{{"ok":true,"result":{{"message_id":{}}}}}"#, 100 + index);
+        ("200 OK", reply)
+    });
+
+    let options =
+        generator::parse_options("[threading]\nreply_to = \"first\"\nmessage_thread_id = 7\n")
+            .unwrap();
+    assert_eq!(
+        options.threading,
+        ThreadingOptions {
+            reply_to: ReplyMode::First,
+            message_thread_id: Some(7),
+        }
+    );
+    let posts = vec!["one".to_string(), "two".to_string(), "three".to_string()];
+    let report = generator::send_to_telegram_threaded(
+        &posts,
+        &base,
+        "TOKEN",
+        "-1001234",
+        false,
+        false,
+        &options.threading,
+    )
+    .unwrap();
+    assert_eq!(report.message_ids, vec![100, 101, 102]);
+
+    let bodies: Vec<String> = server
+        .join()
+        .unwrap()
+        .into_iter()
+        .map(|request| request.body)
+        .collect();
+    assert!(
+        bodies
+            .iter()
+            .all(|body| body.contains("message_thread_id=7"))
+    );
+    assert!(!bodies[0].contains("reply_parameters"));
+    for body in &bodies[1..] {
+        assert!(body.contains("reply_parameters=%7B%22allow_sending_without_reply%22%3Atrue%2C%22message_id%22%3A100%7D"));
+    }
+}
@@ -0,0 +1,47 @@
+use std::path::Path;
+
+use twir_deploy_notify::generator::{self, Glossary, HttpTranslator, Translator};
+
+mod common;
+
+#[test]
+fn translation_memory_translates_offline() {
+    let input = include_str!("2025-07-02-this-week-in-rust.md").to_string();
+    let options = generator::load_options(Path::new("tests/translations/translate.toml")).unwrap();
+    let text = generator::generate_posts_with_options(input, &options)
+        .unwrap()
+        .join("\n");
+    assert!(text.contains(
+        "Крейт этой недели — [ansic](https://crates.io/crates/ansic), процедурный макрос с DSL"
+    ));
+    assert!(text.contains(
+        "Спасибо [Zeon](https://users.rust-lang.org/t/crate-of-the-week/2704/1448) за самовыдвижение\\!"
+    ));
+}
+
+/// Answer a LibreTranslate request by upper-casing its `q` field.
+fn upper_case(_: usize, request: &common::Request) -> (&'static str, String) {
+    let request: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+    assert_eq!(request["target"], "de");
+    assert_eq!(request["api_key"], "secret");
+    let reply = serde_json::json!({
+        "translatedText": request["q"].as_str().unwrap().to_uppercase()
+    });
+    ("200 OK", reply.to_string())
+}
+
+#[test]
+fn http_translator_posts_libretranslate_requests() {
+    let (base, server) = common::serve(2, upper_case);
+    let endpoint = format!("{base}/translate");
+
+    let translator = HttpTranslator::new(&endpoint, "en", "de", Some("secret".to_string()));
+    let (line, errors) = generator::translate_line(
+        "• [Hello](https://example.com/hello) world",
+        &translator as &dyn Translator,
+        &Glossary::default(),
+    );
+    server.join().unwrap();
+    assert!(errors.is_empty(), "{errors:?}");
+    assert_eq!(line, "• [HELLO](https://example.com/hello) WORLD");
+}